@@ -14,7 +14,9 @@ const PROS_ZIP_STR: &str = "build/kernel@4.1.0.zip";
 const PROS_WRAPPER_STR: &str = "build/wrapper.h";
 
 // Types to be included by bindgen
-const WHITELISTED_TYPES: &[&str] = &[];
+const WHITELISTED_TYPES: &[&str] = &[
+    "imu_status_e",
+];
 
 // Enums to be treated as bitfields/bitflags by bindgen
 const BITFIELD_ENUMS: &[&str] = &["ext_adi_port_config_e"];
@@ -31,6 +33,7 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "controller_get_battery_capacity",
     "controller_get_battery_level",
     "controller_get_digital",
+    "controller_get_digital_new_press",
     "controller_is_connected",
     "controller_rumble",
     "controller_set_text",
@@ -52,6 +55,12 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "ext_adi_gyro_get",
     "ext_adi_gyro_reset",
     "ext_adi_gyro_shutdown",
+    "ext_adi_led_clear_all",
+    "ext_adi_led_clear_pixel",
+    "ext_adi_led_init",
+    "ext_adi_led_set",
+    "ext_adi_led_set_all",
+    "ext_adi_led_set_pixel",
     "ext_adi_port_set_config",
     "ext_adi_ultrasonic_get",
     "ext_adi_ultrasonic_init",
@@ -70,6 +79,12 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "adi_gyro_get",
     "adi_gyro_reset",
     "adi_gyro_shutdown",
+    "adi_led_clear_all",
+    "adi_led_clear_pixel",
+    "adi_led_init",
+    "adi_led_set",
+    "adi_led_set_all",
+    "adi_led_set_pixel",
     "adi_port_set_config",
     "adi_ultrasonic_get",
     "adi_ultrasonic_init",
@@ -108,6 +123,7 @@ const WHITELISTED_FUNCS: &[&str] = &[
     "motor_get_direction",
     "motor_get_efficiency",
     "motor_get_encoder_units",
+    "motor_get_faults",
     "motor_get_gearing",
     "motor_get_position",
     "motor_get_power",
@@ -196,6 +212,11 @@ fn main() -> Result<(), io::Error> {
     println!("cargo:rerun-if-changed={}", PROS_ZIP_STR);
     println!("cargo:rerun-if-changed={}", PROS_WRAPPER_STR);
 
+    // the simulated kernel needs neither the PROS firmware nor generated bindings
+    if env::var_os("CARGO_FEATURE_SIM").is_some() {
+        return Ok(());
+    }
+
     // define input paths
     let pros_zip_path = PathBuf::from(PROS_ZIP_STR);
     let wrapper_h_path = PathBuf::from(PROS_WRAPPER_STR);
@@ -1,6 +1,17 @@
 //! # ADI API
 
-use crate::{bindings, error::{PROSErr, PROSResult}, port::AdiPort};
+use core::{mem::ManuallyDrop, ptr};
+use alloc::{boxed::Box, vec};
+use crate::{bindings, color::Rgb, error::{PROSErr, PROSResult}, port::{AdiPort, AdiSmartPort, OwnedAdiPort}};
+
+/// An error from configuring ADI ports, which gives back the ports so that they aren't lost
+#[derive(Debug, PartialEq, Eq)]
+pub struct AdiErr<P = OwnedAdiPort> {
+    /// the reason the ports couldn't be configured
+    pub err: PROSErr,
+    /// the ports that were being configured
+    pub ports: P,
+}
 
 /// An Adi configuration
 #[derive(Debug, Clone, Copy)]
@@ -16,45 +27,529 @@ pub enum AdiConfig {
     DigitalOut = 3,
 }
 
-/// Sets the configurations for a specified ADI port
-pub fn set_config(port: AdiPort, config: AdiConfig)  {
+/// Sets the configurations for a specified ADI port, either on the brain ([`INTERNAL_ADI_PORT`](crate::port::INTERNAL_ADI_PORT)) or on a 3-wire expander plugged into a SmartPort
+///
+/// Only the typed ADI handles (such as [`AdiDigitalOut`]) configure ports, as they consume the [`OwnedAdiPort`] they configure
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+pub(crate) fn set_config(port: &OwnedAdiPort, config: AdiConfig) -> Result<(), PROSErr> {
     // set the config for the adi port
     unsafe {
-        bindings::adi_port_set_config(port as u8, config as u32);
+        bindings::ext_adi_port_set_config(port.smart_port().as_u8(), port.port().as_u8(), config as u32)
+    }.check().map(|_| ())
+}
+
+/// Checks that a pair of ports is on the same SmartPort, with the first in port `A`, `C`, `E` or `G` and the second in the port directly after it
+fn is_adjacent_pair(first: &OwnedAdiPort, second: &OwnedAdiPort) -> bool {
+    first.smart_port() == second.smart_port()
+        && first.port().as_u8() % 2 == 1
+        && second.port().as_u8() == first.port().as_u8() + 1
+}
+
+/// A digital output on an ADI port
+///
+/// Creating the handle configures it's port as `AdiConfig::DigitalOut`, so it can always be written to
+#[derive(Debug)]
+pub struct AdiDigitalOut {
+    /// the ADI port of the output
+    port: OwnedAdiPort,
+}
+
+impl AdiDigitalOut {
+    /// Configures an ADI port, either on the brain or on a 3-wire expander plugged into a SmartPort, as a digital output
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort) -> Result<Self, AdiErr> {
+        match set_config(&port, AdiConfig::DigitalOut) {
+            Ok(()) => Ok(Self { port }),
+            Err(err) => Err(AdiErr { err, ports: port }),
+        }
+    }
+
+    /// Gets the SmartPort and ADI port of the output
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        (self.port.smart_port(), self.port.port())
+    }
+
+    /// Gives back the port of the output, so that it can be used as something else
+    pub fn into_port(self) -> OwnedAdiPort {
+        self.port
+    }
+
+    /// Sends an outbound digital signal
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn write(&mut self, val: bool) -> Result<(), PROSErr> {
+        unsafe {
+            bindings::ext_adi_digital_write(self.port.smart_port().as_u8(), self.port.port().as_u8(), val)
+        }.check().map(|_| ())
     }
 }
 
-/// Sends an outbound digital signal to a **configured** ADI port
+/// A digital input on an ADI port
 ///
-/// **Warning:** ADI port must be configured prior to this function call
-/// 
-/// # Errors
-/// 
-/// - Returns `PROSErr::AddrInUse` if the ADI port is not configured correctly
+/// Creating the handle configures it's port as `AdiConfig::DigitalIn`, so it can always be read from
+#[derive(Debug)]
+pub struct AdiDigitalIn {
+    /// the ADI port of the input
+    port: OwnedAdiPort,
+}
+
+impl AdiDigitalIn {
+    /// Configures an ADI port, either on the brain or on a 3-wire expander plugged into a SmartPort, as a digital input
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort) -> Result<Self, AdiErr> {
+        match set_config(&port, AdiConfig::DigitalIn) {
+            Ok(()) => Ok(Self { port }),
+            Err(err) => Err(AdiErr { err, ports: port }),
+        }
+    }
+
+    /// Gets the SmartPort and ADI port of the input
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        (self.port.smart_port(), self.port.port())
+    }
+
+    /// Gives back the port of the input, so that it can be used as something else
+    pub fn into_port(self) -> OwnedAdiPort {
+        self.port
+    }
+
+    /// Reads an inbound digital signal
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn read(&self) -> Result<bool, PROSErr> {
+        unsafe {
+            bindings::ext_adi_digital_read(self.port.smart_port().as_u8(), self.port.port().as_u8())
+        }.check().map(|val| val != 0)
+    }
+}
+
+/// An analog input on an ADI port, such as a potentiometer, line tracker or light sensor
 ///
-/// # Safety
+/// Creating the handle configures it's port as `AdiConfig::AnalogIn`, so it can always be read from
+#[derive(Debug)]
+pub struct AdiAnalogIn {
+    /// the ADI port of the input
+    port: OwnedAdiPort,
+}
+
+impl AdiAnalogIn {
+    /// Configures an ADI port, either on the brain or on a 3-wire expander plugged into a SmartPort, as an analog input
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort) -> Result<Self, AdiErr> {
+        match set_config(&port, AdiConfig::AnalogIn) {
+            Ok(()) => Ok(Self { port }),
+            Err(err) => Err(AdiErr { err, ports: port }),
+        }
+    }
+
+    /// Gets the SmartPort and ADI port of the input
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        (self.port.smart_port(), self.port.port())
+    }
+
+    /// Gives back the port of the input, so that it can be used as something else
+    pub fn into_port(self) -> OwnedAdiPort {
+        self.port
+    }
+
+    /// Calibrates the analog sensor, returning the new calibration value
+    ///
+    /// The sensor's value is averaged over about `500` samples taken `1`ms apart, which is then used as the zero point for [`read_calibrated`](Self::read_calibrated) and [`read_calibrated_hr`](Self::read_calibrated_hr). The sensor must not be moving while it calibrates, so this is best called during initialisation
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn calibrate(&mut self) -> Result<i32, PROSErr> {
+        unsafe {
+            bindings::ext_adi_analog_calibrate(self.port.smart_port().as_u8(), self.port.port().as_u8())
+        }.check()
+    }
+
+    /// Reads the 12-bit value (`0` to `4095`) of the input
+    ///
+    /// A value of `0` reflects an input voltage of nearly 0V and `4095` reflects nearly 5V
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn read(&self) -> Result<i32, PROSErr> {
+        unsafe {
+            bindings::ext_adi_analog_read(self.port.smart_port().as_u8(), self.port.port().as_u8())
+        }.check()
+    }
+
+    /// Reads the 12-bit value (`-4095` to `4095`) of the input, relative to it's calibration value
+    ///
+    /// [`calibrate`](Self::calibrate) must be called first. Round-off error accumulates when this value is integrated over time, so use [`read_calibrated_hr`](Self::read_calibrated_hr) for that instead
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn read_calibrated(&self) -> Result<i32, PROSErr> {
+        unsafe {
+            bindings::ext_adi_analog_read_calibrated(self.port.smart_port().as_u8(), self.port.port().as_u8())
+        }.check()
+    }
+
+    /// Reads the 16-bit "high resolution" value (`-65536` to `65536`) of the input, relative to it's calibration value
+    ///
+    /// [`calibrate`](Self::calibrate) must be called first. The value is the true value times `16`, which keeps round-off error trivial when the value is integrated over time, such as for gyros and accelerometers
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn read_calibrated_hr(&self) -> Result<i32, PROSErr> {
+        unsafe {
+            bindings::ext_adi_analog_read_calibrated_HR(self.port.smart_port().as_u8(), self.port.port().as_u8())
+        }.check()
+    }
+}
+
+/// A legacy quadrature (optical shaft) encoder on a pair of ADI ports
 ///
-/// This function is marked unsafe due to it depending on the Adi port having previously being configured as the kind of Adi port that is expected by this function
-pub unsafe fn digital_write(port: AdiPort, val: bool) -> Result<(), PROSErr> {
-    // write to the digital ADI output and wrap any errors
-    unsafe {
-        bindings::adi_digital_write(port as u8, val)
-    }.check().map(|_| ())
+/// The encoder is shut down and it's ports are unconfigured when the handle is dropped
+#[derive(Debug)]
+pub struct AdiEncoder {
+    /// the PROS encoder handle (`ext_adi_encoder_t`)
+    handle: i32,
+    /// the ADI port of the encoder's top wire
+    top: OwnedAdiPort,
+    /// the ADI port of the encoder's bottom wire
+    bottom: OwnedAdiPort,
+}
+
+impl AdiEncoder {
+    /// Configures a pair of ADI ports, either on the brain or on a 3-wire expander plugged into a SmartPort, as a legacy quadrature encoder
+    ///
+    /// The top wire (with the removable cover side up) must be in port `A`, `C`, `E` or `G`, with the bottom wire in the port directly after it. A reversed encoder counts in the opposite direction
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the top and bottom ports aren't a valid adjacent pair on the same SmartPort
+    /// - Returns `PROSErr::AddrInUse` if the ADI ports could not be configured as an encoder
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(top: OwnedAdiPort, bottom: OwnedAdiPort, reversed: bool) -> Result<Self, AdiErr<(OwnedAdiPort, OwnedAdiPort)>> {
+        if !is_adjacent_pair(&top, &bottom) {
+            return Err(AdiErr { err: PROSErr::Invalid, ports: (top, bottom) });
+        }
+
+        let handle = unsafe {
+            bindings::ext_adi_encoder_init(top.smart_port().as_u8(), top.port().as_u8(), bottom.port().as_u8(), reversed)
+        }.check();
+
+        match handle {
+            Ok(handle) => Ok(Self { handle, top, bottom }),
+            Err(err) => Err(AdiErr { err, ports: (top, bottom) }),
+        }
+    }
+
+    /// Gets the SmartPort and the ADI ports of the encoder's top and bottom wires
+    pub fn ports(&self) -> (AdiSmartPort, AdiPort, AdiPort) {
+        (self.top.smart_port(), self.top.port(), self.bottom.port())
+    }
+
+    /// Shuts the encoder down and gives back the ports of it's top and bottom wires
+    pub fn into_ports(self) -> (OwnedAdiPort, OwnedAdiPort) {
+        // the encoder is shut down here instead of when it's dropped, so that it's ports can be moved out
+        let encoder = ManuallyDrop::new(self);
+        unsafe {
+            bindings::ext_adi_encoder_shutdown(encoder.handle);
+            (ptr::read(&encoder.top), ptr::read(&encoder.bottom))
+        }
+    }
+
+    /// Gets the number of ticks recorded by the encoder, with `360` ticks per revolution
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn ticks(&self) -> Result<i32, PROSErr> {
+        unsafe {
+            bindings::ext_adi_encoder_get(self.handle)
+        }.check()
+    }
+
+    /// Resets the encoder's ticks to zero
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn reset(&mut self) -> Result<(), PROSErr> {
+        unsafe {
+            bindings::ext_adi_encoder_reset(self.handle)
+        }.check().map(|_| ())
+    }
+}
+
+impl Drop for AdiEncoder {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::ext_adi_encoder_shutdown(self.handle);
+        }
+    }
 }
 
-/// Reads an inbound digital signal from a **configured** ADI port
+/// A legacy ultrasonic rangefinder on a pair of ADI ports
 ///
-/// **Warning:** ADI port must be configured prior to this function call
-/// 
-/// # Errors
-/// 
-/// - Returns `PROSErr::AddrInUse` if the ADI port is not configured correctly
+/// The rangefinder is shut down and it's ports are unconfigured when the handle is dropped
+#[derive(Debug)]
+pub struct AdiUltrasonic {
+    /// the PROS ultrasonic handle (`ext_adi_ultrasonic_t`)
+    handle: i32,
+    /// the ADI port of the orange output (ping) wire
+    ping: OwnedAdiPort,
+    /// the ADI port of the yellow input (echo) wire
+    echo: OwnedAdiPort,
+}
+
+impl AdiUltrasonic {
+    /// Configures a pair of ADI ports, either on the brain or on a 3-wire expander plugged into a SmartPort, as a legacy ultrasonic rangefinder
+    ///
+    /// The orange output (ping) wire must be in port `A`, `C`, `E` or `G`, with the yellow input (echo) wire in the port directly after it
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the ping and echo ports aren't a valid adjacent pair on the same SmartPort
+    /// - Returns `PROSErr::AddrInUse` if the ADI ports could not be configured as an ultrasonic rangefinder
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(ping: OwnedAdiPort, echo: OwnedAdiPort) -> Result<Self, AdiErr<(OwnedAdiPort, OwnedAdiPort)>> {
+        if !is_adjacent_pair(&ping, &echo) {
+            return Err(AdiErr { err: PROSErr::Invalid, ports: (ping, echo) });
+        }
+
+        let handle = unsafe {
+            bindings::ext_adi_ultrasonic_init(ping.smart_port().as_u8(), ping.port().as_u8(), echo.port().as_u8())
+        }.check();
+
+        match handle {
+            Ok(handle) => Ok(Self { handle, ping, echo }),
+            Err(err) => Err(AdiErr { err, ports: (ping, echo) }),
+        }
+    }
+
+    /// Gets the SmartPort and the ADI ports of the rangefinder's ping and echo wires
+    pub fn ports(&self) -> (AdiSmartPort, AdiPort, AdiPort) {
+        (self.ping.smart_port(), self.ping.port(), self.echo.port())
+    }
+
+    /// Shuts the rangefinder down and gives back the ports of it's ping and echo wires
+    pub fn into_ports(self) -> (OwnedAdiPort, OwnedAdiPort) {
+        // the rangefinder is shut down here instead of when it's dropped, so that it's ports can be moved out
+        let ultrasonic = ManuallyDrop::new(self);
+        unsafe {
+            bindings::ext_adi_ultrasonic_shutdown(ultrasonic.handle);
+            (ptr::read(&ultrasonic.ping), ptr::read(&ultrasonic.echo))
+        }
+    }
+
+    /// Gets the distance (in millimetres) to the nearest object, measured from the rangefinder's mounting points
+    ///
+    /// Returns `None` if no echo was heard, meaning there is no object in range. Round and fluffy objects can cause inaccurate readings
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn distance(&self) -> Result<Option<i32>, PROSErr> {
+        let distance = unsafe {
+            bindings::ext_adi_ultrasonic_get(self.handle)
+        }.check()?;
+
+        // PROS reports `0` when there is no echo, and otherwise a distance in tenths of a millimetre
+        Ok((distance != 0).then_some(distance / 10))
+    }
+}
+
+impl Drop for AdiUltrasonic {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::ext_adi_ultrasonic_shutdown(self.handle);
+        }
+    }
+}
+
+/// A legacy yaw-rate gyroscope on an ADI port
 ///
-/// # Safety
+/// The gyro is shut down and it's port is unconfigured when the handle is dropped
+#[derive(Debug)]
+pub struct AdiGyro {
+    /// the PROS gyro handle (`ext_adi_gyro_t`)
+    handle: i32,
+    /// the ADI port of the gyro
+    port: OwnedAdiPort,
+}
+
+impl AdiGyro {
+    /// Configures an ADI port, either on the brain or on a 3-wire expander plugged into a SmartPort, as a legacy gyro with a multiplier applied to all of it's readings
+    ///
+    /// If the port isn't already configured as a gyro, this blocks for about `1300`ms while the gyro calibrates, so the robot must be stationary; this is best called during initialisation. A multiplier of `1.0` leaves readings unscaled
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::AddrInUse` if the ADI port could not be configured as a gyro
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort, multiplier: f64) -> Result<Self, AdiErr> {
+        let handle = unsafe {
+            bindings::ext_adi_gyro_init(port.smart_port().as_u8(), port.port().as_u8(), multiplier)
+        }.check();
+
+        match handle {
+            Ok(handle) => Ok(Self { handle, port }),
+            Err(err) => Err(AdiErr { err, ports: port }),
+        }
+    }
+
+    /// Gets the SmartPort and ADI port of the gyro
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        (self.port.smart_port(), self.port.port())
+    }
+
+    /// Shuts the gyro down and gives back it's port
+    pub fn into_port(self) -> OwnedAdiPort {
+        // the gyro is shut down here instead of when it's dropped, so that it's port can be moved out
+        let gyro = ManuallyDrop::new(self);
+        unsafe {
+            bindings::ext_adi_gyro_shutdown(gyro.handle);
+            ptr::read(&gyro.port)
+        }
+    }
+
+    /// Gets the gyro's heading in degrees (times it's multiplier), which is not bounded and keeps counting past a full rotation
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn heading(&self) -> Result<f64, PROSErr> {
+        unsafe {
+            bindings::ext_adi_gyro_get(self.handle)
+        }.check().map(|tenths| tenths / 10.0)
+    }
+
+    /// Resets the gyro's heading to zero
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn reset(&mut self) -> Result<(), PROSErr> {
+        unsafe {
+            bindings::ext_adi_gyro_reset(self.handle)
+        }.check().map(|_| ())
+    }
+}
+
+impl Drop for AdiGyro {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::ext_adi_gyro_shutdown(self.handle);
+        }
+    }
+}
+
+/// An addressable LED strip on an ADI port
 ///
-/// This function is marked unsafe due to it depending on the Adi port having previously being configured as the kind of Adi port that is expected by this function
-pub unsafe fn digital_read(port: AdiPort) -> Result<bool, PROSErr> {
-    unsafe {
-        bindings::adi_digital_read(port as u8)
-    }.check().map(|val| val != 0)
+/// Colours are set on the handle's own pixel buffer and only sent to the strip, all at once, by [`AdiLed::show`]
+#[derive(Debug)]
+pub struct AdiLed {
+    /// the PROS LED handle (`ext_adi_led_t`)
+    handle: i32,
+    /// the ADI port of the strip
+    port: OwnedAdiPort,
+    /// the colour of each pixel in `0xRRGGBB` form, as PROS expects them
+    pixels: Box<[u32]>,
+}
+
+impl AdiLed {
+    /// Configures an ADI port, either on the brain or on a 3-wire expander plugged into a SmartPort, as an LED strip with a number of pixels that all start off
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the strip has no pixels
+    /// - Returns `PROSErr::AddrInUse` if the ADI port could not be configured as an LED strip
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort, length: usize) -> Result<Self, AdiErr> {
+        if length == 0 {
+            return Err(AdiErr { err: PROSErr::Invalid, ports: port });
+        }
+
+        let handle = unsafe {
+            bindings::ext_adi_led_init(port.smart_port().as_u8(), port.port().as_u8())
+        }.check();
+
+        match handle {
+            Ok(handle) => Ok(Self { handle, port, pixels: vec![0; length].into_boxed_slice() }),
+            Err(err) => Err(AdiErr { err, ports: port }),
+        }
+    }
+
+    /// Gets the SmartPort and ADI port of the strip
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        (self.port.smart_port(), self.port.port())
+    }
+
+    /// Gives back the port of the strip, so that it can be used as something else
+    pub fn into_port(self) -> OwnedAdiPort {
+        self.port
+    }
+
+    /// Gets the amount of pixels in the strip
+    pub fn len(&self) -> usize {
+        self.pixels.len()
+    }
+
+    /// Checks if the strip has no pixels, which is never the case
+    pub fn is_empty(&self) -> bool {
+        self.pixels.is_empty()
+    }
+
+    /// Gets the colour of a pixel in the buffer, or `None` if it's out of range
+    pub fn pixel(&self, index: usize) -> Option<Rgb> {
+        self.pixels.get(index).copied().map(Rgb::from_u32)
+    }
+
+    /// Sets the colour of a single pixel in the buffer
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the pixel is out of range
+    pub fn set_pixel(&mut self, index: usize, color: Rgb) -> Result<(), PROSErr> {
+        let pixel = self.pixels.get_mut(index).ok_or(PROSErr::Invalid)?;
+        *pixel = color.to_u32();
+        Ok(())
+    }
+
+    /// Sets every pixel in the buffer to the same colour
+    pub fn fill(&mut self, color: Rgb) {
+        self.pixels.fill(color.to_u32());
+    }
+
+    /// Turns every pixel in the buffer off
+    pub fn clear(&mut self) {
+        self.pixels.fill(0);
+    }
+
+    /// Sends the whole pixel buffer to the strip in one go
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn show(&mut self) -> Result<(), PROSErr> {
+        unsafe {
+            bindings::ext_adi_led_set(self.handle, self.pixels.as_mut_ptr(), self.pixels.len() as u32)
+        }.check().map(|_| ())
+    }
 }
@@ -7,12 +7,19 @@
 #![allow(dead_code)]
 #![allow(missing_docs)]
 
+#[cfg(not(feature = "sim"))]
 pub use libc::*;
 
 // need to manually declare until https://github.com/rust-lang/libc/issues/1995 is resolved.
+#[cfg(not(feature = "sim"))]
 extern "C" {
     /// Gets a mutable pointer to the C/C++ `errno` value
     pub fn __errno() -> *mut i32;
 }
 
+#[cfg(not(feature = "sim"))]
 include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+
+// swap the PROS kernel out for the host-side simulation
+#[cfg(feature = "sim")]
+pub use crate::sim::kernel::*;
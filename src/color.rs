@@ -0,0 +1,343 @@
+//! # Color API
+
+/// A 24-bit RGB colour, as used by ADI LED strips
+///
+/// The associated constants are the same palette as PROS' `pros/colors.h`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb {
+    /// the red channel of the colour
+    pub r: u8,
+    /// the green channel of the colour
+    pub g: u8,
+    /// the blue channel of the colour
+    pub b: u8,
+}
+
+impl Rgb {
+    /// Creates a colour from it's red, green and blue channels
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// Creates a colour from it's `0xRRGGBB` form (like PROS' `color_e_t`), ignoring the top byte
+    pub const fn from_u32(color: u32) -> Self {
+        Self {
+            r: (color >> 16) as u8,
+            g: (color >> 8) as u8,
+            b: color as u8,
+        }
+    }
+
+    /// Gets the `0xRRGGBB` form (like PROS' `color_e_t`) of the colour
+    pub const fn to_u32(self) -> u32 {
+        (self.r as u32) << 16 | (self.g as u32) << 8 | self.b as u32
+    }
+}
+
+impl From<u32> for Rgb {
+    fn from(color: u32) -> Self {
+        Self::from_u32(color)
+    }
+}
+
+impl From<Rgb> for u32 {
+    fn from(color: Rgb) -> Self {
+        color.to_u32()
+    }
+}
+
+/// The `pros/colors.h` palette
+impl Rgb {
+    /// `COLOR_ALICE_BLUE` (`#F0F8FF`)
+    pub const ALICE_BLUE: Self = Self::from_u32(0xF0F8FF);
+    /// `COLOR_ANTIQUE_WHITE` (`#FAEBD7`)
+    pub const ANTIQUE_WHITE: Self = Self::from_u32(0xFAEBD7);
+    /// `COLOR_AQUA` (`#00FFFF`)
+    pub const AQUA: Self = Self::from_u32(0x00FFFF);
+    /// `COLOR_AQUAMARINE` (`#7FFFD4`)
+    pub const AQUAMARINE: Self = Self::from_u32(0x7FFFD4);
+    /// `COLOR_AZURE` (`#F0FFFF`)
+    pub const AZURE: Self = Self::from_u32(0xF0FFFF);
+    /// `COLOR_BEIGE` (`#F5F5DC`)
+    pub const BEIGE: Self = Self::from_u32(0xF5F5DC);
+    /// `COLOR_BISQUE` (`#FFE4C4`)
+    pub const BISQUE: Self = Self::from_u32(0xFFE4C4);
+    /// `COLOR_BLACK` (`#000000`)
+    pub const BLACK: Self = Self::from_u32(0x000000);
+    /// `COLOR_BLANCHED_ALMOND` (`#FFEBCD`)
+    pub const BLANCHED_ALMOND: Self = Self::from_u32(0xFFEBCD);
+    /// `COLOR_BLUE` (`#0000FF`)
+    pub const BLUE: Self = Self::from_u32(0x0000FF);
+    /// `COLOR_BLUE_VIOLET` (`#8A2BE2`)
+    pub const BLUE_VIOLET: Self = Self::from_u32(0x8A2BE2);
+    /// `COLOR_BROWN` (`#A52A2A`)
+    pub const BROWN: Self = Self::from_u32(0xA52A2A);
+    /// `COLOR_BURLY_WOOD` (`#DEB887`)
+    pub const BURLY_WOOD: Self = Self::from_u32(0xDEB887);
+    /// `COLOR_CADET_BLUE` (`#5F9EA0`)
+    pub const CADET_BLUE: Self = Self::from_u32(0x5F9EA0);
+    /// `COLOR_CHARTREUSE` (`#7FFF00`)
+    pub const CHARTREUSE: Self = Self::from_u32(0x7FFF00);
+    /// `COLOR_CHOCOLATE` (`#D2691E`)
+    pub const CHOCOLATE: Self = Self::from_u32(0xD2691E);
+    /// `COLOR_CORAL` (`#FF7F50`)
+    pub const CORAL: Self = Self::from_u32(0xFF7F50);
+    /// `COLOR_CORNFLOWER_BLUE` (`#6495ED`)
+    pub const CORNFLOWER_BLUE: Self = Self::from_u32(0x6495ED);
+    /// `COLOR_CORNSILK` (`#FFF8DC`)
+    pub const CORNSILK: Self = Self::from_u32(0xFFF8DC);
+    /// `COLOR_CRIMSON` (`#DC143C`)
+    pub const CRIMSON: Self = Self::from_u32(0xDC143C);
+    /// `COLOR_CYAN` (`#00FFFF`)
+    pub const CYAN: Self = Self::from_u32(0x00FFFF);
+    /// `COLOR_DARK_BLUE` (`#00008B`)
+    pub const DARK_BLUE: Self = Self::from_u32(0x00008B);
+    /// `COLOR_DARK_CYAN` (`#008B8B`)
+    pub const DARK_CYAN: Self = Self::from_u32(0x008B8B);
+    /// `COLOR_DARK_GOLDENROD` (`#B8860B`)
+    pub const DARK_GOLDENROD: Self = Self::from_u32(0xB8860B);
+    /// `COLOR_DARK_GRAY` (`#A9A9A9`)
+    pub const DARK_GRAY: Self = Self::from_u32(0xA9A9A9);
+    /// `COLOR_DARK_GREY` (`#A9A9A9`)
+    pub const DARK_GREY: Self = Self::from_u32(0xA9A9A9);
+    /// `COLOR_DARK_GREEN` (`#006400`)
+    pub const DARK_GREEN: Self = Self::from_u32(0x006400);
+    /// `COLOR_DARK_KHAKI` (`#BDB76B`)
+    pub const DARK_KHAKI: Self = Self::from_u32(0xBDB76B);
+    /// `COLOR_DARK_MAGENTA` (`#8B008B`)
+    pub const DARK_MAGENTA: Self = Self::from_u32(0x8B008B);
+    /// `COLOR_DARK_OLIVE_GREEN` (`#556B2F`)
+    pub const DARK_OLIVE_GREEN: Self = Self::from_u32(0x556B2F);
+    /// `COLOR_DARK_ORANGE` (`#FF8C00`)
+    pub const DARK_ORANGE: Self = Self::from_u32(0xFF8C00);
+    /// `COLOR_DARK_ORCHID` (`#9932CC`)
+    pub const DARK_ORCHID: Self = Self::from_u32(0x9932CC);
+    /// `COLOR_DARK_RED` (`#8B0000`)
+    pub const DARK_RED: Self = Self::from_u32(0x8B0000);
+    /// `COLOR_DARK_SALMON` (`#E9967A`)
+    pub const DARK_SALMON: Self = Self::from_u32(0xE9967A);
+    /// `COLOR_DARK_SEA_GREEN` (`#8FBC8F`)
+    pub const DARK_SEA_GREEN: Self = Self::from_u32(0x8FBC8F);
+    /// `COLOR_DARK_SLATE_GRAY` (`#2F4F4F`)
+    pub const DARK_SLATE_GRAY: Self = Self::from_u32(0x2F4F4F);
+    /// `COLOR_DARK_SLATE_GREY` (`#2F4F4F`)
+    pub const DARK_SLATE_GREY: Self = Self::from_u32(0x2F4F4F);
+    /// `COLOR_DARK_TURQUOISE` (`#00CED1`)
+    pub const DARK_TURQUOISE: Self = Self::from_u32(0x00CED1);
+    /// `COLOR_DARK_VIOLET` (`#9400D3`)
+    pub const DARK_VIOLET: Self = Self::from_u32(0x9400D3);
+    /// `COLOR_DEEP_PINK` (`#FF1493`)
+    pub const DEEP_PINK: Self = Self::from_u32(0xFF1493);
+    /// `COLOR_DEEP_SKY_BLUE` (`#00BFFF`)
+    pub const DEEP_SKY_BLUE: Self = Self::from_u32(0x00BFFF);
+    /// `COLOR_DIM_GRAY` (`#696969`)
+    pub const DIM_GRAY: Self = Self::from_u32(0x696969);
+    /// `COLOR_DIM_GREY` (`#696969`)
+    pub const DIM_GREY: Self = Self::from_u32(0x696969);
+    /// `COLOR_DODGER_BLUE` (`#1E90FF`)
+    pub const DODGER_BLUE: Self = Self::from_u32(0x1E90FF);
+    /// `COLOR_FIRE_BRICK` (`#B22222`)
+    pub const FIRE_BRICK: Self = Self::from_u32(0xB22222);
+    /// `COLOR_FLORAL_WHITE` (`#FFFAF0`)
+    pub const FLORAL_WHITE: Self = Self::from_u32(0xFFFAF0);
+    /// `COLOR_FOREST_GREEN` (`#228B22`)
+    pub const FOREST_GREEN: Self = Self::from_u32(0x228B22);
+    /// `COLOR_FUCHSIA` (`#FF00FF`)
+    pub const FUCHSIA: Self = Self::from_u32(0xFF00FF);
+    /// `COLOR_GAINSBORO` (`#DCDCDC`)
+    pub const GAINSBORO: Self = Self::from_u32(0xDCDCDC);
+    /// `COLOR_GHOST_WHITE` (`#F8F8FF`)
+    pub const GHOST_WHITE: Self = Self::from_u32(0xF8F8FF);
+    /// `COLOR_GOLD` (`#FFD700`)
+    pub const GOLD: Self = Self::from_u32(0xFFD700);
+    /// `COLOR_GOLDENROD` (`#DAA520`)
+    pub const GOLDENROD: Self = Self::from_u32(0xDAA520);
+    /// `COLOR_GRAY` (`#808080`)
+    pub const GRAY: Self = Self::from_u32(0x808080);
+    /// `COLOR_GREY` (`#808080`)
+    pub const GREY: Self = Self::from_u32(0x808080);
+    /// `COLOR_GREEN` (`#008000`)
+    pub const GREEN: Self = Self::from_u32(0x008000);
+    /// `COLOR_GREEN_YELLOW` (`#ADFF2F`)
+    pub const GREEN_YELLOW: Self = Self::from_u32(0xADFF2F);
+    /// `COLOR_HONEYDEW` (`#F0FFF0`)
+    pub const HONEYDEW: Self = Self::from_u32(0xF0FFF0);
+    /// `COLOR_HOT_PINK` (`#FF69B4`)
+    pub const HOT_PINK: Self = Self::from_u32(0xFF69B4);
+    /// `COLOR_INDIAN_RED` (`#CD5C5C`)
+    pub const INDIAN_RED: Self = Self::from_u32(0xCD5C5C);
+    /// `COLOR_INDIGO` (`#4B0082`)
+    pub const INDIGO: Self = Self::from_u32(0x4B0082);
+    /// `COLOR_IVORY` (`#FFFFF0`)
+    pub const IVORY: Self = Self::from_u32(0xFFFFF0);
+    /// `COLOR_KHAKI` (`#F0E68C`)
+    pub const KHAKI: Self = Self::from_u32(0xF0E68C);
+    /// `COLOR_LAVENDER` (`#E6E6FA`)
+    pub const LAVENDER: Self = Self::from_u32(0xE6E6FA);
+    /// `COLOR_LAVENDER_BLUSH` (`#FFF0F5`)
+    pub const LAVENDER_BLUSH: Self = Self::from_u32(0xFFF0F5);
+    /// `COLOR_LAWN_GREEN` (`#7CFC00`)
+    pub const LAWN_GREEN: Self = Self::from_u32(0x7CFC00);
+    /// `COLOR_LEMON_CHIFFON` (`#FFFACD`)
+    pub const LEMON_CHIFFON: Self = Self::from_u32(0xFFFACD);
+    /// `COLOR_LIGHT_BLUE` (`#ADD8E6`)
+    pub const LIGHT_BLUE: Self = Self::from_u32(0xADD8E6);
+    /// `COLOR_LIGHT_CORAL` (`#F08080`)
+    pub const LIGHT_CORAL: Self = Self::from_u32(0xF08080);
+    /// `COLOR_LIGHT_CYAN` (`#E0FFFF`)
+    pub const LIGHT_CYAN: Self = Self::from_u32(0xE0FFFF);
+    /// `COLOR_LIGHT_GOLDENROD_YELLOW` (`#FAFAD2`)
+    pub const LIGHT_GOLDENROD_YELLOW: Self = Self::from_u32(0xFAFAD2);
+    /// `COLOR_LIGHT_GREEN` (`#90EE90`)
+    pub const LIGHT_GREEN: Self = Self::from_u32(0x90EE90);
+    /// `COLOR_LIGHT_GRAY` (`#D3D3D3`)
+    pub const LIGHT_GRAY: Self = Self::from_u32(0xD3D3D3);
+    /// `COLOR_LIGHT_GREY` (`#D3D3D3`)
+    pub const LIGHT_GREY: Self = Self::from_u32(0xD3D3D3);
+    /// `COLOR_LIGHT_PINK` (`#FFB6C1`)
+    pub const LIGHT_PINK: Self = Self::from_u32(0xFFB6C1);
+    /// `COLOR_LIGHT_SALMON` (`#FFA07A`)
+    pub const LIGHT_SALMON: Self = Self::from_u32(0xFFA07A);
+    /// `COLOR_LIGHT_SEA_GREEN` (`#20B2AA`)
+    pub const LIGHT_SEA_GREEN: Self = Self::from_u32(0x20B2AA);
+    /// `COLOR_LIGHT_SKY_BLUE` (`#87CEFA`)
+    pub const LIGHT_SKY_BLUE: Self = Self::from_u32(0x87CEFA);
+    /// `COLOR_LIGHT_SLATE_GRAY` (`#778899`)
+    pub const LIGHT_SLATE_GRAY: Self = Self::from_u32(0x778899);
+    /// `COLOR_LIGHT_SLATE_GREY` (`#778899`)
+    pub const LIGHT_SLATE_GREY: Self = Self::from_u32(0x778899);
+    /// `COLOR_LIGHT_STEEL_BLUE` (`#B0C4DE`)
+    pub const LIGHT_STEEL_BLUE: Self = Self::from_u32(0xB0C4DE);
+    /// `COLOR_LIGHT_YELLOW` (`#FFFFE0`)
+    pub const LIGHT_YELLOW: Self = Self::from_u32(0xFFFFE0);
+    /// `COLOR_LIME` (`#00FF00`)
+    pub const LIME: Self = Self::from_u32(0x00FF00);
+    /// `COLOR_LIME_GREEN` (`#32CD32`)
+    pub const LIME_GREEN: Self = Self::from_u32(0x32CD32);
+    /// `COLOR_LINEN` (`#FAF0E6`)
+    pub const LINEN: Self = Self::from_u32(0xFAF0E6);
+    /// `COLOR_MAGENTA` (`#FF00FF`)
+    pub const MAGENTA: Self = Self::from_u32(0xFF00FF);
+    /// `COLOR_MAROON` (`#800000`)
+    pub const MAROON: Self = Self::from_u32(0x800000);
+    /// `COLOR_MEDIUM_AQUAMARINE` (`#66CDAA`)
+    pub const MEDIUM_AQUAMARINE: Self = Self::from_u32(0x66CDAA);
+    /// `COLOR_MEDIUM_BLUE` (`#0000CD`)
+    pub const MEDIUM_BLUE: Self = Self::from_u32(0x0000CD);
+    /// `COLOR_MEDIUM_ORCHID` (`#BA55D3`)
+    pub const MEDIUM_ORCHID: Self = Self::from_u32(0xBA55D3);
+    /// `COLOR_MEDIUM_PURPLE` (`#9370DB`)
+    pub const MEDIUM_PURPLE: Self = Self::from_u32(0x9370DB);
+    /// `COLOR_MEDIUM_SEA_GREEN` (`#3CB371`)
+    pub const MEDIUM_SEA_GREEN: Self = Self::from_u32(0x3CB371);
+    /// `COLOR_MEDIUM_SLATE_BLUE` (`#7B68EE`)
+    pub const MEDIUM_SLATE_BLUE: Self = Self::from_u32(0x7B68EE);
+    /// `COLOR_MEDIUM_SPRING_GREEN` (`#00FA9A`)
+    pub const MEDIUM_SPRING_GREEN: Self = Self::from_u32(0x00FA9A);
+    /// `COLOR_MEDIUM_TURQUOISE` (`#48D1CC`)
+    pub const MEDIUM_TURQUOISE: Self = Self::from_u32(0x48D1CC);
+    /// `COLOR_MEDIUM_VIOLET_RED` (`#C71585`)
+    pub const MEDIUM_VIOLET_RED: Self = Self::from_u32(0xC71585);
+    /// `COLOR_MIDNIGHT_BLUE` (`#191970`)
+    pub const MIDNIGHT_BLUE: Self = Self::from_u32(0x191970);
+    /// `COLOR_MINT_CREAM` (`#F5FFFA`)
+    pub const MINT_CREAM: Self = Self::from_u32(0xF5FFFA);
+    /// `COLOR_MISTY_ROSE` (`#FFE4E1`)
+    pub const MISTY_ROSE: Self = Self::from_u32(0xFFE4E1);
+    /// `COLOR_MOCCASIN` (`#FFE4B5`)
+    pub const MOCCASIN: Self = Self::from_u32(0xFFE4B5);
+    /// `COLOR_NAVAJO_WHITE` (`#FFDEAD`)
+    pub const NAVAJO_WHITE: Self = Self::from_u32(0xFFDEAD);
+    /// `COLOR_NAVY` (`#000080`)
+    pub const NAVY: Self = Self::from_u32(0x000080);
+    /// `COLOR_OLD_LACE` (`#FDF5E6`)
+    pub const OLD_LACE: Self = Self::from_u32(0xFDF5E6);
+    /// `COLOR_OLIVE` (`#808000`)
+    pub const OLIVE: Self = Self::from_u32(0x808000);
+    /// `COLOR_OLIVE_DRAB` (`#6B8E23`)
+    pub const OLIVE_DRAB: Self = Self::from_u32(0x6B8E23);
+    /// `COLOR_ORANGE` (`#FFA500`)
+    pub const ORANGE: Self = Self::from_u32(0xFFA500);
+    /// `COLOR_ORANGE_RED` (`#FF4500`)
+    pub const ORANGE_RED: Self = Self::from_u32(0xFF4500);
+    /// `COLOR_ORCHID` (`#DA70D6`)
+    pub const ORCHID: Self = Self::from_u32(0xDA70D6);
+    /// `COLOR_PALE_GOLDENROD` (`#EEE8AA`)
+    pub const PALE_GOLDENROD: Self = Self::from_u32(0xEEE8AA);
+    /// `COLOR_PALE_GREEN` (`#98FB98`)
+    pub const PALE_GREEN: Self = Self::from_u32(0x98FB98);
+    /// `COLOR_PALE_TURQUOISE` (`#AFEEEE`)
+    pub const PALE_TURQUOISE: Self = Self::from_u32(0xAFEEEE);
+    /// `COLOR_PALE_VIOLET_RED` (`#DB7093`)
+    pub const PALE_VIOLET_RED: Self = Self::from_u32(0xDB7093);
+    /// `COLOR_PAPAY_WHIP` (`#FFEFD5`)
+    pub const PAPAY_WHIP: Self = Self::from_u32(0xFFEFD5);
+    /// `COLOR_PEACH_PUFF` (`#FFDAB9`)
+    pub const PEACH_PUFF: Self = Self::from_u32(0xFFDAB9);
+    /// `COLOR_PERU` (`#CD853F`)
+    pub const PERU: Self = Self::from_u32(0xCD853F);
+    /// `COLOR_PINK` (`#FFC0CB`)
+    pub const PINK: Self = Self::from_u32(0xFFC0CB);
+    /// `COLOR_PLUM` (`#DDA0DD`)
+    pub const PLUM: Self = Self::from_u32(0xDDA0DD);
+    /// `COLOR_POWDER_BLUE` (`#B0E0E6`)
+    pub const POWDER_BLUE: Self = Self::from_u32(0xB0E0E6);
+    /// `COLOR_PURPLE` (`#800080`)
+    pub const PURPLE: Self = Self::from_u32(0x800080);
+    /// `COLOR_RED` (`#FF0000`)
+    pub const RED: Self = Self::from_u32(0xFF0000);
+    /// `COLOR_ROSY_BROWN` (`#BC8F8F`)
+    pub const ROSY_BROWN: Self = Self::from_u32(0xBC8F8F);
+    /// `COLOR_ROYAL_BLUE` (`#4169E1`)
+    pub const ROYAL_BLUE: Self = Self::from_u32(0x4169E1);
+    /// `COLOR_SADDLE_BROWN` (`#8B4513`)
+    pub const SADDLE_BROWN: Self = Self::from_u32(0x8B4513);
+    /// `COLOR_SALMON` (`#FA8072`)
+    pub const SALMON: Self = Self::from_u32(0xFA8072);
+    /// `COLOR_SANDY_BROWN` (`#F4A460`)
+    pub const SANDY_BROWN: Self = Self::from_u32(0xF4A460);
+    /// `COLOR_SEA_GREEN` (`#2E8B57`)
+    pub const SEA_GREEN: Self = Self::from_u32(0x2E8B57);
+    /// `COLOR_SEASHELL` (`#FFF5EE`)
+    pub const SEASHELL: Self = Self::from_u32(0xFFF5EE);
+    /// `COLOR_SIENNA` (`#A0522D`)
+    pub const SIENNA: Self = Self::from_u32(0xA0522D);
+    /// `COLOR_SILVER` (`#C0C0C0`)
+    pub const SILVER: Self = Self::from_u32(0xC0C0C0);
+    /// `COLOR_SKY_BLUE` (`#87CEEB`)
+    pub const SKY_BLUE: Self = Self::from_u32(0x87CEEB);
+    /// `COLOR_SLATE_BLUE` (`#6A5ACD`)
+    pub const SLATE_BLUE: Self = Self::from_u32(0x6A5ACD);
+    /// `COLOR_SLATE_GRAY` (`#708090`)
+    pub const SLATE_GRAY: Self = Self::from_u32(0x708090);
+    /// `COLOR_SLATE_GREY` (`#708090`)
+    pub const SLATE_GREY: Self = Self::from_u32(0x708090);
+    /// `COLOR_SNOW` (`#FFFAFA`)
+    pub const SNOW: Self = Self::from_u32(0xFFFAFA);
+    /// `COLOR_SPRING_GREEN` (`#00FF7F`)
+    pub const SPRING_GREEN: Self = Self::from_u32(0x00FF7F);
+    /// `COLOR_STEEL_BLUE` (`#4682B4`)
+    pub const STEEL_BLUE: Self = Self::from_u32(0x4682B4);
+    /// `COLOR_TAN` (`#D2B48C`)
+    pub const TAN: Self = Self::from_u32(0xD2B48C);
+    /// `COLOR_TEAL` (`#008080`)
+    pub const TEAL: Self = Self::from_u32(0x008080);
+    /// `COLOR_THISTLE` (`#D8BFD8`)
+    pub const THISTLE: Self = Self::from_u32(0xD8BFD8);
+    /// `COLOR_TOMATO` (`#FF6347`)
+    pub const TOMATO: Self = Self::from_u32(0xFF6347);
+    /// `COLOR_TURQUOISE` (`#40E0D0`)
+    pub const TURQUOISE: Self = Self::from_u32(0x40E0D0);
+    /// `COLOR_VIOLET` (`#EE82EE`)
+    pub const VIOLET: Self = Self::from_u32(0xEE82EE);
+    /// `COLOR_WHEAT` (`#F5DEB3`)
+    pub const WHEAT: Self = Self::from_u32(0xF5DEB3);
+    /// `COLOR_WHITE` (`#FFFFFF`)
+    pub const WHITE: Self = Self::from_u32(0xFFFFFF);
+    /// `COLOR_WHITE_SMOKE` (`#F5F5F5`)
+    pub const WHITE_SMOKE: Self = Self::from_u32(0xF5F5F5);
+    /// `COLOR_YELLOW` (`#FFFF00`)
+    pub const YELLOW: Self = Self::from_u32(0xFFFF00);
+    /// `COLOR_YELLOW_GREEN` (`#9ACD32`)
+    pub const YELLOW_GREEN: Self = Self::from_u32(0x9ACD32);
+}
@@ -1,8 +1,15 @@
 //! # Controller API
 
-use alloc::ffi::CString;
+use alloc::{boxed::Box, collections::VecDeque, ffi::CString, string::String, vec::Vec};
 
-use crate::{bindings, error::{PROSErr, PROSResult}};
+use crate::{bindings, error::{PROSErr, PROSResult}, rtos};
+
+/// The amount of lines of text on the controller's screen
+pub const LCD_LINES: u8 = 3;
+/// The amount of columns of text on the controller's screen
+pub const LCD_COLUMNS: u8 = 19;
+/// The minimum amount of milliseconds between updates to the controller's screen, as PROS drops any that are sent faster
+pub const LCD_UPDATE_INTERVAL: u32 = 50;
 
 /// A controller that you can read from
 #[derive(Debug, Clone, Copy)]
@@ -15,7 +22,7 @@ pub enum Controller {
 }
 
 /// An Analog Joystick on the Controller
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum ControllerAnalog {
     /// The x axis of the left joystick
@@ -29,7 +36,7 @@ pub enum ControllerAnalog {
 }
 
 /// A digital (button) on the controller
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum ControllerDigital {
     /// The first trigger on the left side of the controller
@@ -58,12 +65,64 @@ pub enum ControllerDigital {
     A,
 }
 
+impl ControllerAnalog {
+    /// Every joystick axis on the controller
+    pub const ALL: [Self; 4] = [Self::LeftX, Self::LeftY, Self::RightX, Self::RightY];
+}
+
+impl ControllerDigital {
+    /// Every button on the controller
+    pub const ALL: [Self; 12] = [
+        Self::L1, Self::L2, Self::R1, Self::R2,
+        Self::Up, Self::Down, Self::Left, Self::Right,
+        Self::X, Self::B, Self::Y, Self::A,
+    ];
+
+    /// Gets the index of the button within [`ControllerDigital::ALL`]
+    fn index(self) -> usize {
+        self as usize - Self::L1 as usize
+    }
+}
+
+/// Checks if a controller is connected to the brain
+///
+/// # Errors
+///
+/// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn is_connected(controller: Controller) -> Result<bool, PROSErr> {
+    unsafe {
+        bindings::controller_is_connected(controller as u32)
+    }.check().map(|x| x != 0)
+}
+
+/// Returns the battery level of a controller
+///
+/// # Errors
+///
+/// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn battery_level(controller: Controller) -> Result<i32, PROSErr> {
+    unsafe {
+        bindings::controller_get_battery_level(controller as u32)
+    }.check()
+}
+
+/// Returns the battery capacity of a controller
+///
+/// # Errors
+///
+/// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn battery_capacity(controller: Controller) -> Result<i32, PROSErr> {
+    unsafe {
+        bindings::controller_get_battery_capacity(controller as u32)
+    }.check()
+}
+
 /// Returns the analog value of a controller
 ///
 /// # Errors
 ///
 /// Returns `PROSErr::Access` if another resource is currently trying to access the controller
-/// Returns `0` if the controller is not connected
+/// Returns `0` if the controller is not connected, so use [`is_connected`] to tell a disconnected controller apart from a centred joystick
 pub fn get_analog(controller: Controller, analog: ControllerAnalog) -> Result<i8, PROSErr> {
     unsafe {
         bindings::controller_get_analog(controller as u32, analog as u32)
@@ -75,24 +134,498 @@ pub fn get_analog(controller: Controller, analog: ControllerAnalog) -> Result<i8
 /// # Errors
 ///
 /// Returns `PROSErr::Access` if another resource is currently trying to access the controller
-/// Returns `false` if the controller is not connected
+/// Returns `false` if the controller is not connected, so use [`is_connected`] to tell a disconnected controller apart from a released button
 pub fn get_digital(controller: Controller, digital: ControllerDigital) -> Result<bool, PROSErr> {
     unsafe {
         bindings::controller_get_digital(controller as u32, digital as u32)
     }.check().map(|x| x != 0)
 }
 
-/// Rumbles the controller
+/// Returns if a controller button has been newly pressed since the last time this was called for it
 ///
-/// The rumble pattern is a string consisting of the characters '.', '-', and ' ', where dots are short rumbles, dashes are long rumbles, and spaces are pauses. Maximum supported length is 8 characters
+/// PROS tracks the previous state of each button globally, so only one task should call this for any given button
 ///
 /// # Errors
 ///
 /// Returns `PROSErr::Access` if another resource is currently trying to access the controller
-pub fn rumble(controller: Controller, rumble_pattern: &str) -> Result<(), PROSErr> {
+/// Returns `false` if the controller is not connected
+pub fn get_digital_new_press(controller: Controller, digital: ControllerDigital) -> Result<bool, PROSErr> {
+    unsafe {
+        bindings::controller_get_digital_new_press(controller as u32, digital as u32)
+    }.check().map(|x| x != 0)
+}
+
+/// A snapshot of every joystick axis and button on a controller, sampled at once
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ControllerState {
+    /// the value of each joystick axis, in the order of [`ControllerAnalog::ALL`]
+    analog: [i8; 4],
+    /// if each button is held down, in the order of [`ControllerDigital::ALL`]
+    digital: [bool; 12],
+}
+
+impl ControllerState {
+    /// Samples every joystick axis and button on a controller
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+    /// Returns a state with everything released and centred if the controller is not connected
+    pub fn sample(controller: Controller) -> Result<Self, PROSErr> {
+        let mut state = Self::default();
+        for (value, analog) in state.analog.iter_mut().zip(ControllerAnalog::ALL) {
+            *value = get_analog(controller, analog)?;
+        }
+        for (pressed, digital) in state.digital.iter_mut().zip(ControllerDigital::ALL) {
+            *pressed = get_digital(controller, digital)?;
+        }
+
+        Ok(state)
+    }
+
+    /// Gets the value of a joystick axis from `-127` to `127`
+    pub fn analog(&self, analog: ControllerAnalog) -> i8 {
+        self.analog[analog as usize]
+    }
+
+    /// Gets if a button is held down
+    pub fn digital(&self, digital: ControllerDigital) -> bool {
+        self.digital[digital.index()]
+    }
+}
+
+/// A change in a controller button found by a [`ButtonTracker`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonEvent {
+    /// The button was just pressed
+    Pressed(ControllerDigital),
+    /// The button has now been held down for the tracker's hold time (reported once per press)
+    Held(ControllerDigital),
+    /// The button was just released after being held down for a number of milliseconds
+    Released(ControllerDigital, u32),
+}
+
+/// Tracks the buttons of a controller between samples, turning them into press, hold and release events
+#[derive(Debug, Clone)]
+pub struct ButtonTracker {
+    /// the controller whose buttons are tracked
+    controller: Controller,
+    /// how many milliseconds a button must be held down for before it's reported as held
+    hold_time: u32,
+    /// the latest sampled state of the controller
+    state: ControllerState,
+    /// the time (in milliseconds) each button was pressed, and if it's been reported as held yet
+    pressed: [Option<(u32, bool)>; 12],
+}
+
+impl ButtonTracker {
+    /// Creates a new tracker for a controller's buttons, which reports buttons as held once they've been held down for `hold_time` milliseconds
+    pub fn new(controller: Controller, hold_time: u32) -> Self {
+        Self {
+            controller,
+            hold_time,
+            state: ControllerState::default(),
+            pressed: [None; 12],
+        }
+    }
+
+    /// Gets the controller whose buttons are tracked
+    pub fn controller(&self) -> Controller {
+        self.controller
+    }
+
+    /// Gets the latest sampled state of the controller
+    pub fn state(&self) -> &ControllerState {
+        &self.state
+    }
+
+    /// Samples the controller and reports the events of every button since the last sample
+    ///
+    /// Meant to be called every iteration of a control loop
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+    pub fn poll(&mut self) -> Result<Box<[ButtonEvent]>, PROSErr> {
+        let state = ControllerState::sample(self.controller)?;
+        Ok(self.update(state, rtos::millis()))
+    }
+
+    /// Reports the events of every button between the last state and a newly sampled one, taken at a time in milliseconds
+    pub fn update(&mut self, state: ControllerState, now: u32) -> Box<[ButtonEvent]> {
+        self.state = state;
+
+        let mut events = Vec::new();
+        for (pressed, digital) in self.pressed.iter_mut().zip(ControllerDigital::ALL) {
+            match (*pressed, state.digital(digital)) {
+                (None, true) => {
+                    *pressed = Some((now, false));
+                    events.push(ButtonEvent::Pressed(digital));
+                },
+                (Some((since, held)), true) => if !held && now.wrapping_sub(since) >= self.hold_time {
+                    *pressed = Some((since, true));
+                    events.push(ButtonEvent::Held(digital));
+                },
+                (Some((since, _)), false) => {
+                    *pressed = None;
+                    events.push(ButtonEvent::Released(digital, now.wrapping_sub(since)));
+                },
+                (None, false) => (),
+            }
+        }
+
+        events.into()
+    }
+}
+
+/// The maximum amount of steps in a [`RumblePattern`]
+pub const RUMBLE_MAX_STEPS: usize = 8;
+
+/// An error building or parsing a [`RumblePattern`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RumbleErr {
+    /// The pattern would have more than [`RUMBLE_MAX_STEPS`] steps
+    TooLong,
+    /// The pattern contains a character other than `.`, `-` or a space
+    InvalidStep(char),
+}
+
+/// A controller rumble pattern of up to [`RUMBLE_MAX_STEPS`] short rumbles, long rumbles and pauses
+///
+/// Patterns are either built step by step (starting from [`RumblePattern::new`]) or parsed from PROS' string form, where `.` is a short rumble, `-` is a long rumble and a space is a pause
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RumblePattern {
+    /// the characters of the pattern's string form
+    steps: [u8; RUMBLE_MAX_STEPS],
+    /// the amount of steps in the pattern
+    len: usize,
+}
+
+impl RumblePattern {
+    /// A single short rumble
+    pub const SHORT: Self = Self::from_steps(b".");
+    /// A single long rumble
+    pub const LONG: Self = Self::from_steps(b"-");
+    /// Two short rumbles
+    pub const DOUBLE: Self = Self::from_steps(b"..");
+    /// Three short rumbles
+    pub const TRIPLE: Self = Self::from_steps(b"...");
+    /// Three long rumbles, such as for a warning that the match is almost over
+    pub const ALERT: Self = Self::from_steps(b"---");
+
+    /// Creates a pattern from steps that are already known to be valid
+    const fn from_steps(steps: &[u8]) -> Self {
+        let mut pattern = Self::new();
+        while pattern.len < steps.len() {
+            pattern.steps[pattern.len] = steps[pattern.len];
+            pattern.len += 1;
+        }
+        pattern
+    }
+
+    /// Creates a new pattern with no steps
+    pub const fn new() -> Self {
+        Self {
+            steps: [0; RUMBLE_MAX_STEPS],
+            len: 0,
+        }
+    }
+
+    /// Parses a pattern from PROS' string form
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RumbleErr::InvalidStep` if the string contains a character other than `.`, `-` or a space
+    /// - Returns `RumbleErr::TooLong` if the string is longer than [`RUMBLE_MAX_STEPS`]
+    pub fn parse(pattern: &str) -> Result<Self, RumbleErr> {
+        pattern.chars().try_fold(Self::new(), |pattern, step| match step {
+            '.' | '-' | ' ' => pattern.push(step as u8),
+            _ => Err(RumbleErr::InvalidStep(step)),
+        })
+    }
+
+    /// Adds a step to the end of the pattern
+    fn push(mut self, step: u8) -> Result<Self, RumbleErr> {
+        if self.len == RUMBLE_MAX_STEPS {
+            return Err(RumbleErr::TooLong);
+        }
+
+        self.steps[self.len] = step;
+        self.len += 1;
+        Ok(self)
+    }
+
+    /// Adds a short rumble to the end of the pattern
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RumbleErr::TooLong` if the pattern already has [`RUMBLE_MAX_STEPS`] steps
+    pub fn short(self) -> Result<Self, RumbleErr> {
+        self.push(b'.')
+    }
+
+    /// Adds a long rumble to the end of the pattern
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RumbleErr::TooLong` if the pattern already has [`RUMBLE_MAX_STEPS`] steps
+    pub fn long(self) -> Result<Self, RumbleErr> {
+        self.push(b'-')
+    }
+
+    /// Adds a pause to the end of the pattern
+    ///
+    /// # Errors
+    ///
+    /// - Returns `RumbleErr::TooLong` if the pattern already has [`RUMBLE_MAX_STEPS`] steps
+    pub fn pause(self) -> Result<Self, RumbleErr> {
+        self.push(b' ')
+    }
+
+    /// Gets the amount of steps in the pattern
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Checks if the pattern has no steps
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets PROS' string form of the pattern
+    pub fn as_str(&self) -> &str {
+        // the steps are only ever ascii `.`, `-` and spaces
+        core::str::from_utf8(&self.steps[..self.len]).unwrap()
+    }
+}
+
+impl Default for RumblePattern {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TryFrom<&str> for RumblePattern {
+    type Error = RumbleErr;
+
+    fn try_from(pattern: &str) -> Result<Self, Self::Error> {
+        Self::parse(pattern)
+    }
+}
+
+/// Rumbles the controller with a pattern
+///
+/// # Errors
+///
+/// Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn rumble(controller: Controller, pattern: RumblePattern) -> Result<(), PROSErr> {
     // rumble the controller
     unsafe {
-        let rumble_pattern = CString::new(rumble_pattern).unwrap();
-        bindings::controller_rumble(controller as u32, rumble_pattern.as_ptr() as *const u8)
+        let pattern = CString::new(pattern.as_str()).unwrap();
+        bindings::controller_rumble(controller as u32, pattern.as_ptr() as *const u8)
     }.check().map(|_| ())
 }
+
+/// Truncates text so that it fits on the controller's screen from a line and column, dropping any nul characters
+///
+/// The screen's lines are limited in bytes rather than characters, so the text is cut at the last whole character that fits
+///
+/// # Errors
+///
+/// - Returns `PROSErr::Invalid` if the line or column is off the screen
+fn fit_text(line: u8, col: u8, text: &str) -> Result<String, PROSErr> {
+    if line >= LCD_LINES || col >= LCD_COLUMNS {
+        return Err(PROSErr::Invalid);
+    }
+
+    let mut text = text.replace('\0', "");
+    let mut end = (LCD_COLUMNS - col) as usize;
+    if text.len() > end {
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+    Ok(text)
+}
+
+/// Writes text to the controller's screen starting at a line (`0..3`) and column (`0..19`), truncating whatever doesn't fit
+///
+/// PROS silently drops screen updates sent less than [`LCD_UPDATE_INTERVAL`] milliseconds apart; use an [`LcdWriter`] to queue them instead
+///
+/// # Errors
+///
+/// - Returns `PROSErr::Invalid` if the line or column is off the screen
+/// - Returns `PROSErr::Access` if another resource is currently trying to access the controller
+/// - Returns `PROSErr::Again` if the text could not be sent to the controller
+pub fn set_text(controller: Controller, line: u8, col: u8, text: &str) -> Result<(), PROSErr> {
+    let text = CString::new(fit_text(line, col, text)?).unwrap();
+    unsafe {
+        bindings::controller_set_text(controller as u32, line, col, text.as_ptr() as *const u8)
+    }.check().map(|_| ())
+}
+
+/// Clears a line (`0..3`) of the controller's screen
+///
+/// # Errors
+///
+/// - Returns `PROSErr::Invalid` if the line is off the screen
+/// - Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn clear_line(controller: Controller, line: u8) -> Result<(), PROSErr> {
+    if line >= LCD_LINES {
+        return Err(PROSErr::Invalid);
+    }
+
+    unsafe {
+        bindings::controller_clear_line(controller as u32, line)
+    }.check().map(|_| ())
+}
+
+/// Clears the controller's whole screen
+///
+/// # Errors
+///
+/// - Returns `PROSErr::Access` if another resource is currently trying to access the controller
+pub fn clear(controller: Controller) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::controller_clear(controller as u32)
+    }.check().map(|_| ())
+}
+
+/// An update to the controller's screen waiting in an [`LcdWriter`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LcdCommand {
+    /// Writes (already truncated) text starting at a line and column
+    SetText {
+        /// the line of the text
+        line: u8,
+        /// the column of the text's first character
+        col: u8,
+        /// the text to write
+        text: String,
+    },
+    /// Clears a single line
+    ClearLine(u8),
+    /// Clears the whole screen
+    Clear,
+}
+
+/// A queue of updates to a controller's screen that sends them at most once every [`LCD_UPDATE_INTERVAL`] milliseconds so none of them get dropped
+///
+/// Writing text to the same line and column as the last queued write to that line is merged into it instead of queueing another, so frequently refreshed values (like a timer) never fall behind
+#[derive(Debug, Clone)]
+pub struct LcdWriter {
+    /// the controller whose screen is written to
+    controller: Controller,
+    /// the updates waiting to be sent, oldest first
+    queue: VecDeque<LcdCommand>,
+    /// the time (in milliseconds) the last update was sent
+    last_sent: Option<u32>,
+}
+
+impl LcdWriter {
+    /// Creates a new writer with nothing queued for a controller's screen
+    pub fn new(controller: Controller) -> Self {
+        Self {
+            controller,
+            queue: VecDeque::new(),
+            last_sent: None,
+        }
+    }
+
+    /// Gets the controller whose screen is written to
+    pub fn controller(&self) -> Controller {
+        self.controller
+    }
+
+    /// Gets the updates waiting to be sent, oldest first
+    pub fn pending(&self) -> &VecDeque<LcdCommand> {
+        &self.queue
+    }
+
+    /// Queues text to be written starting at a line (`0..3`) and column (`0..19`), truncating whatever doesn't fit
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the line or column is off the screen
+    pub fn set_text(&mut self, line: u8, col: u8, text: &str) -> Result<(), PROSErr> {
+        let text = fit_text(line, col, text)?;
+
+        // text can only be merged into the last write to it's line, as merging it into an earlier one would send it before the writes after that
+        let last = self.queue.iter_mut().rev().find_map(|command| match command {
+            LcdCommand::SetText { line: l, col: c, text: queued } if *l == line => Some((*c, queued)),
+            _ => None,
+        });
+        if let Some((c, queued)) = last {
+            if c == col {
+                // the new text is written over the queued text, which still shows wherever the new text is shorter
+                let overlaid = text.chars().chain(queued.chars().skip(text.chars().count())).collect::<String>();
+                *queued = fit_text(line, col, &overlaid)?;
+                return Ok(());
+            }
+        }
+
+        // an earlier write to the same position can only be dropped if the new text covers all of it
+        let width = text.chars().count();
+        self.queue.retain(|command| !matches!(
+            command,
+            LcdCommand::SetText { line: l, col: c, text: queued } if *l == line && *c == col && queued.chars().count() <= width
+        ));
+        self.queue.push_back(LcdCommand::SetText { line, col, text });
+        Ok(())
+    }
+
+    /// Queues a line (`0..3`) to be cleared, dropping any queued text on it
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the line is off the screen
+    pub fn clear_line(&mut self, line: u8) -> Result<(), PROSErr> {
+        if line >= LCD_LINES {
+            return Err(PROSErr::Invalid);
+        }
+
+        self.queue.retain(|command| !matches!(command, LcdCommand::SetText { line: l, .. } | LcdCommand::ClearLine(l) if *l == line));
+        self.queue.push_back(LcdCommand::ClearLine(line));
+        Ok(())
+    }
+
+    /// Queues the whole screen to be cleared, dropping every other queued update
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.queue.push_back(LcdCommand::Clear);
+    }
+
+    /// Sends the oldest queued update if at least [`LCD_UPDATE_INTERVAL`] milliseconds have passed since the last one, returning if anything was sent
+    ///
+    /// Meant to be called every iteration of a control loop; an update that fails to send stays queued and is retried by the next poll
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Access` if another resource is currently trying to access the controller
+    /// - Returns `PROSErr::Again` if the text could not be sent to the controller
+    pub fn poll(&mut self) -> Result<bool, PROSErr> {
+        let now = rtos::millis();
+        if self.last_sent.is_some_and(|last| now.wrapping_sub(last) < LCD_UPDATE_INTERVAL) {
+            return Ok(false);
+        }
+        let Some(command) = self.queue.pop_front() else {
+            return Ok(false);
+        };
+
+        let result = match &command {
+            LcdCommand::SetText { line, col, text } => set_text(self.controller, *line, *col, text),
+            LcdCommand::ClearLine(line) => clear_line(self.controller, *line),
+            LcdCommand::Clear => clear(self.controller),
+        };
+        self.last_sent = Some(now);
+
+        match result {
+            Ok(()) => Ok(true),
+            Err(err) => {
+                self.queue.push_front(command);
+                Err(err)
+            },
+        }
+    }
+}
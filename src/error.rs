@@ -2,12 +2,6 @@
 
 use crate::bindings;
 
-// need to manually declare until https://github.com/rust-lang/libc/issues/1995 is resolved.
-extern "C" {
-    /// Gets a mutable pointer to the C/C++ `errno` value
-    pub fn __errno() -> *mut i32;
-}
-
 /// Possible PROS Errors
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(i32)]
@@ -46,7 +40,7 @@ pub enum PROSErr {
 pub fn from_errno() -> PROSErr {
     unsafe {
         // get errno pointer
-        let errno: *const i32 = __errno() as *const i32;
+        let errno: *const i32 = bindings::__errno() as *const i32;
         // cast the errno to a PROSErr through pointer magic
         *(errno as *const PROSErr) // transmute would also work here too but this looks cooler
     }
@@ -54,7 +48,7 @@ pub fn from_errno() -> PROSErr {
 
 /// A type which may contain a [`PROSErr`] depending upon a sentinel value that represents errors
 ///
-/// Implementations are provided for `i32`, `f64` and `*mut T` values based on either PRO's sentinel error values (`PROS_ERR` or `PROS_ERR_F` in C/C++) or a NULL pointer
+/// Implementations are provided for `i32`, `u32` (C enums), `f64` and `*mut T` values based on either PRO's sentinel error values (`PROS_ERR` or `PROS_ERR_F` in C/C++) or a NULL pointer
 pub trait PROSResult: Sized {
     /// Checks if the type is a valid (success value), giving an appropriate
     /// error otherwise.
@@ -71,6 +65,16 @@ impl PROSResult for i32 {
     }
 }
 
+impl PROSResult for u32 {
+    fn check(self) -> Result<Self, PROSErr> {
+        if self == bindings::PROS_ERR_ as u32 {
+            Err(from_errno())
+        } else {
+            Ok(self)
+        }
+    }
+}
+
 impl PROSResult for f64 {
     fn check(self) -> Result<Self, PROSErr> {
         if self == bindings::PROS_ERR_F_ {
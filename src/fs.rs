@@ -1,7 +1,7 @@
 //! Utilities for safely accessing the file system of v5 brain
 
 use core::ffi::CStr;
-use alloc::{boxed::Box, ffi::CString, string::{String, ToString}};
+use alloc::{boxed::Box, ffi::CString, string::{String, ToString}, vec::Vec};
 use byte_strings::c_str;
 use crate::{bindings, error::{self, PROSErr, PROSResult}};
 
@@ -63,11 +63,11 @@ pub struct FileWrite {
     pointer: *mut bindings::FILE,
 }
 
-// /// A safe wrapper over a C filestream with read permissions
-// pub struct FileRead {
-//     /// internal pointer to the C file
-//     pointer: *mut bindings::FILE,
-// }
+/// A safe wrapper over a C filestream with read permissions
+pub struct FileRead {
+    /// internal pointer to the C file
+    pointer: *mut bindings::FILE,
+}
 
 impl FileWrite {
     /// Creates a new file
@@ -193,3 +193,85 @@ impl Drop for FileWrite {
         }
     }
 }
+
+impl FileRead {
+    /// Opens a file that **already** exists for reading
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::IO` on low-level hardware I/O error
+    /// - Returns `PROSErr::Invalid` when the length or path is invalid
+    /// - Returns `PROSErr::NoEntry` when the path cannot be found
+    /// - Returns `PROSErr::Access` when access is denied
+    /// - Returns `PROSErr::NXIO` when the drive number is invalid or not an FAT32 drive
+    /// - Returns `PROSErr::NoBuffSpace` when the drive has no work area
+    /// - Returns `PROSErr::NoMoreFiles` when there are too many open files
+    ///
+    /// # Warning
+    /// **All** paths **must** start with `/usd/`, if you don't it won't work
+    pub fn open(path: &str) -> Result<FileRead, PROSErr> {
+        // cast the strings to c strings
+        let path = CString::new(path).unwrap();
+
+        // obtain the file pointer
+        let pointer = unsafe {
+            bindings::fopen(
+                path.as_ptr() as *const u8,
+                c_str!("r").as_ptr() as *const u8,
+            )
+        };
+
+        // check for errors
+        if pointer.is_null() {
+            return Err(error::from_errno());
+        }
+
+        // return the valid pointer
+        Ok(FileRead {
+            pointer
+        })
+    }
+
+    /// Reads the rest of the file into a string, replacing any invalid UTF-8 with `U+FFFD`
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::IO` on low-level hardware I/O error, such as the SD card being removed
+    pub fn read_to_string(&mut self) -> Result<String, PROSErr> {
+        /// The size of the read buffer
+        const BUFF_SIZE: usize = 256;
+
+        let mut bytes = Vec::new();
+        let mut buffer = [0u8; BUFF_SIZE];
+        loop {
+            // read the next chunk of the file
+            let read = unsafe {
+                bindings::fread(buffer.as_mut_ptr() as *mut _, 1, BUFF_SIZE, self.pointer)
+            };
+            bytes.extend_from_slice(&buffer[..read]);
+
+            // a short read means either the end of the file or an error
+            if read < BUFF_SIZE {
+                if unsafe { bindings::ferror(self.pointer) } != 0 {
+                    return Err(error::from_errno());
+                }
+                break;
+            }
+        }
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+
+    /// Closes the filestream safely
+    pub fn close(self) {
+        // closed when dropped
+    }
+}
+
+impl Drop for FileRead {
+    fn drop(&mut self) {
+        unsafe {
+            bindings::fclose(self.pointer);
+        }
+    }
+}
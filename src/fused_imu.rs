@@ -0,0 +1,153 @@
+//! # Fused Inertial Sensor API
+
+use alloc::{boxed::Box, vec::Vec};
+use libm::{fabs, fmod};
+use crate::{error::PROSErr, imu, port::SmartPort};
+
+/// The health of a single Inertial Sensor in a [`FusedImu`], as of it's last read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SensorHealth {
+    /// The sensor's reading was used
+    Healthy,
+    /// The sensor is unplugged (or isn't an Inertial Sensor)
+    Disconnected,
+    /// The sensor's reading was further than the divergence threshold from the others
+    Diverged,
+    /// The sensor couldn't be read, such as while it's calibrating
+    Unavailable(PROSErr),
+}
+
+/// A single Inertial Sensor in a [`FusedImu`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FusedSensor {
+    /// the port of the sensor
+    pub port: SmartPort,
+    /// how much the sensor's reading counts towards the fused reading, relative to the others
+    pub weight: f64,
+    /// the health of the sensor as of the last read
+    pub health: SensorHealth,
+    /// the sensor's rotation (in degrees) as of the last read, or `None` if it couldn't be read
+    pub rotation: Option<f64>,
+}
+
+/// Several Inertial Sensors read together as one, so that a single sensor drifting or disconnecting doesn't throw off the robot's heading
+///
+/// Every read takes the weighted median of the sensors' rotations as a reference (the lower of the two middle readings when they're evenly split), rejects any sensor further than the divergence threshold from it, and averages the rest by weight. Rejection is decided again on every read, so a sensor that comes back into line is used again
+#[derive(Debug, Clone)]
+pub struct FusedImu {
+    /// the sensors being fused
+    sensors: Box<[FusedSensor]>,
+    /// the furthest (in degrees) a sensor's rotation can be from the reference before it's rejected
+    threshold: f64,
+}
+
+impl FusedImu {
+    /// Creates a new fused sensor from the port and weight of each Inertial Sensor, rejecting sensors more than `threshold` degrees from the others
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if a weight is negative or not finite, no sensor has a weight above `0`, or the threshold is negative or NaN
+    pub fn new(sensors: &[(SmartPort, f64)], threshold: f64) -> Result<Self, PROSErr> {
+        if sensors.iter().any(|&(_, weight)| !weight.is_finite() || weight < 0.0)
+            || !sensors.iter().any(|&(_, weight)| weight > 0.0)
+            || threshold.is_nan()
+            || threshold < 0.0
+        {
+            return Err(PROSErr::Invalid);
+        }
+
+        Ok(Self {
+            sensors: sensors
+                .iter()
+                .map(|&(port, weight)| FusedSensor {
+                    port,
+                    weight,
+                    health: SensorHealth::Healthy,
+                    rotation: None,
+                })
+                .collect(),
+            threshold,
+        })
+    }
+
+    /// Gets every sensor along with it's health and reading as of the last read, for logging
+    pub fn sensors(&self) -> &[FusedSensor] {
+        &self.sensors
+    }
+
+    /// Reads every sensor and gets the weighted average of the healthy sensors' rotations in degrees
+    ///
+    /// Like [`imu::get_rotation`], this is unbounded and keeps counting past a full turn
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if no sensor could be read
+    /// - Returns `PROSErr::Invalid` if only sensors with a weight of `0` could be read
+    pub fn rotation(&mut self) -> Result<f64, PROSErr> {
+        for sensor in self.sensors.iter_mut() {
+            let reading = imu::get_rotation(sensor.port);
+            sensor.rotation = reading.ok();
+            sensor.health = match reading {
+                Ok(_) => SensorHealth::Healthy,
+                Err(PROSErr::NoDev) => SensorHealth::Disconnected,
+                Err(err) => SensorHealth::Unavailable(err),
+            };
+        }
+
+        let Some(reference) = self.weighted_median() else {
+            // sensors with no weight can still be read, but can't make up a reading on their own
+            return Err(if self.sensors.iter().any(|sensor| sensor.rotation.is_some()) {
+                PROSErr::Invalid
+            } else {
+                PROSErr::NoDev
+            });
+        };
+
+        let mut total = 0.0;
+        let mut weights = 0.0;
+        for sensor in self.sensors.iter_mut() {
+            let Some(rotation) = sensor.rotation else {
+                continue;
+            };
+
+            if fabs(rotation - reference) > self.threshold {
+                sensor.health = SensorHealth::Diverged;
+            } else {
+                total += rotation * sensor.weight;
+                weights += sensor.weight;
+            }
+        }
+
+        // the reference sensor itself is always within the threshold
+        Ok(total / weights)
+    }
+
+    /// Reads every sensor and gets the weighted average of the healthy sensors' headings, bounded by `0..360`
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if no sensor could be read
+    /// - Returns `PROSErr::Invalid` if only sensors with a weight of `0` could be read
+    pub fn heading(&mut self) -> Result<f64, PROSErr> {
+        // averaging rotations rather than headings avoids jumps where the heading wraps around
+        let heading = fmod(self.rotation()?, 360.0);
+        Ok(if heading < 0.0 { heading + 360.0 } else { heading })
+    }
+
+    /// Gets the weighted median of the sensors' last readings, ignoring sensors with no weight
+    fn weighted_median(&self) -> Option<f64> {
+        let mut readings = self.sensors
+            .iter()
+            .filter(|sensor| sensor.weight > 0.0)
+            .filter_map(|sensor| sensor.rotation.map(|rotation| (rotation, sensor.weight)))
+            .collect::<Vec<_>>();
+        readings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        let half = readings.iter().map(|(_, weight)| weight).sum::<f64>() / 2.0;
+        let mut weights = 0.0;
+        readings.into_iter().find_map(|(rotation, weight)| {
+            weights += weight;
+            (weights >= half).then_some(rotation)
+        })
+    }
+}
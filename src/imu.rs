@@ -1,10 +1,15 @@
 //! # Inertial Sensor API
 
-use crate::{bindings, error::{PROSErr, PROSResult}, port::SmartPort};
+use core::ops::{BitAnd, BitOr};
+use alloc::format;
+use crate::{bindings, error::{self, PROSErr, PROSResult}, fs::{FileRead, FileWrite}, port::SmartPort, rtos};
+
+/// How often (in milliseconds) [`calibrate_blocking`] polls the status of the Inertial Sensor
+const CALIBRATION_POLL_INTERVAL: u32 = 10;
 
 /// Calibrate the IMU Sensor
 ///
-/// Calibration takes approximately `2` seconds, but this function only blocks until the IMU status flag is set properly to E_IMU_STATUS_CALIBRATING, with a minimum blocking time of 5ms
+/// Calibration takes approximately `2` seconds, but this function only blocks until the IMU status flag is set properly to E_IMU_STATUS_CALIBRATING, with a minimum blocking time of 5ms; use [`calibrate_blocking`] to wait until calibration has finished
 ///
 /// # Errors
 ///
@@ -16,6 +21,116 @@ pub fn reset(port: SmartPort) -> Result<(), PROSErr> {
     }.check().map(|_| ())
 }
 
+/// The status flags of an Inertial Sensor
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImuStatus(u32);
+
+impl ImuStatus {
+    /// No flags, meaning the sensor is ready
+    pub const NONE: Self = Self(bindings::imu_status_e_E_IMU_STATUS_READY);
+    /// The sensor is calibrating
+    pub const CALIBRATING: Self = Self(bindings::imu_status_e_E_IMU_STATUS_CALIBRATING);
+    /// Every status flag
+    pub const ALL: Self = Self(Self::CALIBRATING.0);
+
+    /// Creates a set of status flags from it's raw bits, dropping any bits that aren't known flags
+    pub const fn from_bits_truncate(bits: u32) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Gets the raw bits of the set of status flags
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Checks if **all** of the flags in `other` are set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Checks if the sensor is calibrating
+    pub const fn is_calibrating(self) -> bool {
+        self.contains(Self::CALIBRATING)
+    }
+
+    /// Checks if the sensor is ready, which is whenever it isn't calibrating
+    pub const fn is_ready(self) -> bool {
+        !self.is_calibrating()
+    }
+}
+
+impl BitOr for ImuStatus {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for ImuStatus {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+/// Gets the Inertial Sensor's status flags
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+pub fn get_status(port: SmartPort) -> Result<ImuStatus, PROSErr> {
+    let status = unsafe {
+        bindings::imu_get_status(port as u8)
+    };
+
+    // `E_IMU_STATUS_ERROR` is the status PROS returns when it fails to read the status
+    if status == bindings::imu_status_e_E_IMU_STATUS_ERROR {
+        Err(error::from_errno())
+    } else {
+        Ok(ImuStatus::from_bits_truncate(status))
+    }
+}
+
+/// Calibrates the IMU Sensor and blocks until it's finished calibrating, or `timeout` milliseconds have passed
+///
+/// This returns as soon as the sensor is ready (which is usually after about `2` seconds), so there's no need for a fixed delay after it. If the sensor is already calibrating, this just waits for that calibration to finish
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating after the timeout
+pub fn calibrate_blocking(port: SmartPort, timeout: u32) -> Result<(), PROSErr> {
+    let start = rtos::millis();
+    match reset(port) {
+        Ok(()) | Err(PROSErr::Again) => (),
+        Err(err) => return Err(err),
+    }
+
+    while get_status(port)?.is_calibrating() {
+        if rtos::millis().wrapping_sub(start) >= timeout {
+            return Err(PROSErr::Again);
+        }
+        rtos::task_delay(CALIBRATION_POLL_INTERVAL);
+    }
+    Ok(())
+}
+
+/// Gets the total rotation of the Inertial Sensor about it's z-axis since it was last calibrated or tared, in degrees
+///
+/// Unlike the heading, this value is unbounded and keeps counting past a full turn, so it never jumps between `0` and `360` (which makes it the value to use for turning PID). Clockwise rotations are represented with positive degree values
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn get_rotation(port: SmartPort) -> Result<f64, PROSErr> {
+    unsafe {
+        bindings::imu_get_rotation(port as u8)
+    }.check()
+}
+
 /// Gets the Inertial Sensor's heading relative to the initial direction of it's x-axis
 ///
 /// This value is bounded by `0..=360`///
@@ -84,6 +199,148 @@ pub fn tare(port: SmartPort) -> Result<(), PROSErr> {
     }.check().map(|_| ())
 }
 
+/// Resets the Inertial Sensor's heading to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_heading(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_heading(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Resets the Inertial Sensor's rotation to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_rotation(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_rotation(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Resets the Inertial Sensor's pitch to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_pitch(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_pitch(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Resets the Inertial Sensor's roll to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_roll(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_roll(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Resets the Inertial Sensor's yaw to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_yaw(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_yaw(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Resets the Inertial Sensor's pitch, roll and yaw to 0, leaving it's other values untouched
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn tare_euler(port: SmartPort) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_tare_euler(port as u8)
+    }.check().map(|_| ())
+}
+
+/// Sets the Inertial Sensor's current heading to a target value in degrees, leaving it's other values untouched
+///
+/// The target is clamped to `0..=360`
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_heading(port: SmartPort, target: f64) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_set_heading(port as u8, target)
+    }.check().map(|_| ())
+}
+
+/// Sets the Inertial Sensor's current rotation to a target value in degrees, leaving it's other values untouched
+///
+/// The rotation is unbounded, so the target can be any number of degrees
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_rotation(port: SmartPort, target: f64) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_set_rotation(port as u8, target)
+    }.check().map(|_| ())
+}
+
+/// Sets the Inertial Sensor's current pitch to a target value in degrees, leaving it's other values untouched
+///
+/// The target is clamped to `-180..=180`
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_pitch(port: SmartPort, target: f64) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_set_pitch(port as u8, target)
+    }.check().map(|_| ())
+}
+
+/// Sets the Inertial Sensor's current roll to a target value in degrees, leaving it's other values untouched
+///
+/// The target is clamped to `-180..=180`
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_roll(port: SmartPort, target: f64) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_set_roll(port as u8, target)
+    }.check().map(|_| ())
+}
+
+/// Sets the Inertial Sensor's current yaw to a target value in degrees, leaving it's other values untouched
+///
+/// The target is clamped to `-180..=180`
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_yaw(port: SmartPort, target: f64) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::imu_set_yaw(port as u8, target)
+    }.check().map(|_| ())
+}
+
 /// Sets the Inertial Sensor's refresh interval in milliseconds
 ///
 /// # Errors
@@ -124,3 +381,232 @@ pub fn get_accel(port: SmartPort) -> Result<Acceleration, PROSErr> {
         })
     }
 }
+
+/// The orientation of an Inertial Sensor as a unit quaternion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quaternion {
+    /// the x component of the quaternion
+    pub x: f64,
+    /// the y component of the quaternion
+    pub y: f64,
+    /// the z component of the quaternion
+    pub z: f64,
+    /// the w (real) component of the quaternion
+    pub w: f64,
+}
+
+/// Gets the Inertial Sensor's orientation as a quaternion
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn get_quaternion(port: SmartPort) -> Result<Quaternion, PROSErr> {
+    unsafe {
+        let quaternion = bindings::imu_get_quaternion(port as u8);
+        let x = quaternion.x.check()?; // check for errors
+
+        Ok(Quaternion {
+            x,
+            y: quaternion.y,
+            z: quaternion.z,
+            w: quaternion.w,
+        })
+    }
+}
+
+/// The orientation of an Inertial Sensor as euler angles in degrees, each bounded by `-180..=180`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Euler {
+    /// the rotation about the y axis
+    pub pitch: f64,
+    /// the rotation about the x axis
+    pub roll: f64,
+    /// the rotation about the z axis
+    pub yaw: f64,
+}
+
+/// Gets the Inertial Sensor's pitch, roll and yaw all at once
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn get_euler(port: SmartPort) -> Result<Euler, PROSErr> {
+    unsafe {
+        let euler = bindings::imu_get_euler(port as u8);
+        let pitch = euler.pitch.check()?; // check for errors
+
+        Ok(Euler {
+            pitch,
+            roll: euler.roll,
+            yaw: euler.yaw,
+        })
+    }
+}
+
+/// Sets the Inertial Sensor's current pitch, roll and yaw to target values in degrees all at once
+///
+/// Each target is clamped to `-180..=180`
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn set_euler(port: SmartPort, target: Euler) -> Result<(), PROSErr> {
+    let target = bindings::euler_s {
+        pitch: target.pitch,
+        roll: target.roll,
+        yaw: target.yaw,
+    };
+
+    unsafe {
+        bindings::imu_set_euler(port as u8, target)
+    }.check().map(|_| ())
+}
+
+/// Raw Gyroscope values, as rates of rotation in degrees per second
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GyroRate {
+    /// rate of rotation about the x axis
+    pub x: f64,
+    /// rate of rotation about the y axis
+    pub y: f64,
+    /// rate of rotation about the z axis
+    pub z: f64,
+}
+
+/// Gets the Inertial Sensor's raw gyroscope values
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn get_gyro_rate(port: SmartPort) -> Result<GyroRate, PROSErr> {
+    unsafe {
+        let rate = bindings::imu_get_gyro_rate(port as u8);
+        rate.x.check()?; // check for errors
+
+        Ok(GyroRate {
+            x: rate.x,
+            y: rate.y,
+            z: rate.z,
+        })
+    }
+}
+
+/// The bias of an Inertial Sensor while stationary, as estimated by [`estimate_bias`]
+///
+/// This is the rate the sensor's rotation drifts at, measured from the rotation itself rather than from the gyroscope's rates, as PROS doesn't document the gyroscope's axes as sharing a sign with [`get_rotation`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GyroBias {
+    /// the rate (in degrees per second) that the rotation drifts at, with the same clockwise-positive sign as [`get_rotation`]
+    pub drift: f64,
+}
+
+impl GyroBias {
+    /// Loads a bias previously saved with [`GyroBias::save`]
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of [`FileRead::open`] and [`FileRead::read_to_string`] if the file can't be read
+    /// - Returns `PROSErr::Invalid` if the file doesn't contain a saved bias, or the saved bias isn't finite
+    ///
+    /// # Warning
+    /// **All** paths **must** start with `/usd/`, if you don't it won't work
+    pub fn load(path: &str) -> Result<Self, PROSErr> {
+        let contents = FileRead::open(path)?.read_to_string()?;
+
+        let drift = contents.trim().parse::<f64>().map_err(|_| PROSErr::Invalid)?;
+        if !drift.is_finite() {
+            return Err(PROSErr::Invalid);
+        }
+        Ok(Self { drift })
+    }
+
+    /// Saves the bias to a file so that it can be loaded on the next boot, replacing the file if it already exists
+    ///
+    /// # Errors
+    ///
+    /// - Returns the errors of [`FileWrite::create`] and [`FileWrite::write`] if the file can't be written
+    ///
+    /// # Warning
+    /// **All** paths **must** start with `/usd/`, if you don't it won't work
+    pub fn save(&self, path: &str) -> Result<(), PROSErr> {
+        FileWrite::create(path)?.write(&format!("{}\n", self.drift))
+    }
+}
+
+/// Estimates the bias of the Inertial Sensor from how far it's rotation drifts over `duration` milliseconds (and at least `1`ms), blocking until it's done
+///
+/// The robot **must** be stationary for the whole estimate, so this is best run while the robot is disabled
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+/// - Returns `PROSErr::Again` if the sensor is still calibrating
+pub fn estimate_bias(port: SmartPort, duration: u32) -> Result<GyroBias, PROSErr> {
+    let start = rtos::millis();
+    let start_rotation = get_rotation(port)?;
+
+    rtos::task_delay(duration.max(1));
+
+    let elapsed = rtos::millis().wrapping_sub(start) as f64 / 1000.0;
+    let drift = (get_rotation(port)? - start_rotation) / elapsed;
+
+    Ok(GyroBias { drift })
+}
+
+/// An Inertial Sensor whose rotation is compensated for the drift caused by it's gyroscope's bias
+///
+/// The drift is taken to build up linearly from when the compensation starts (or is restarted), at the bias's [`drift`](GyroBias::drift) rate
+#[derive(Debug, Clone, Copy)]
+pub struct DriftCompensator {
+    /// the port of the sensor
+    port: SmartPort,
+    /// the bias of the sensor, including the rate it drifts at
+    bias: GyroBias,
+    /// the time (in milliseconds) the drift started building up from
+    start: u32,
+}
+
+impl DriftCompensator {
+    /// Starts compensating the rotation of the Inertial Sensor on a port for a bias from now
+    pub fn new(port: SmartPort, bias: GyroBias) -> Self {
+        Self {
+            port,
+            bias,
+            start: rtos::millis(),
+        }
+    }
+
+    /// Gets the port of the sensor
+    pub fn port(&self) -> SmartPort {
+        self.port
+    }
+
+    /// Gets the bias being compensated for
+    pub fn bias(&self) -> &GyroBias {
+        &self.bias
+    }
+
+    /// Restarts the drift from now, which must be done whenever the sensor's rotation is tared or set
+    pub fn restart(&mut self) {
+        self.start = rtos::millis();
+    }
+
+    /// Gets the drift (in degrees) that has built up since the compensation started
+    pub fn drift(&self) -> f64 {
+        self.bias.drift * rtos::millis().wrapping_sub(self.start) as f64 / 1000.0
+    }
+
+    /// Gets the sensor's compensated rotation in degrees, which (like [`get_rotation`]) is unbounded
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if the port cannot be configured as a Inertial Sensor
+    /// - Returns `PROSErr::Again` if the sensor is still calibrating
+    pub fn rotation(&self) -> Result<f64, PROSErr> {
+        Ok(get_rotation(self.port)? - self.drift())
+    }
+}
@@ -0,0 +1,213 @@
+//! # Input Shaping API
+//!
+//! Turns raw joystick values from [`controller::get_analog`](crate::controller::get_analog) into normalised outputs from `-1.0` to `1.0`, ready for [`voltage`] and [`motor::move_voltage`](crate::motor::move_voltage)
+
+use libm::{copysignf, expf, fabsf, sqrtf};
+use crate::{error::PROSErr, rtos};
+
+/// Normalises a raw joystick value from `-127` to `127` into `-1.0` to `1.0`
+pub fn normalise(raw: i8) -> f32 {
+    (raw as f32 / 127.0).clamp(-1.0, 1.0)
+}
+
+/// Converts a normalised output from `-1.0` to `1.0` into a motor voltage from `-12000` to `12000`
+pub fn voltage(output: f32) -> i32 {
+    (output.clamp(-1.0, 1.0) * 12000.0) as i32
+}
+
+/// A drive curve that trades precision at low speeds for sensitivity at high speeds
+///
+/// Every curve maps `0.0` to `0.0` and `1.0` to `1.0`, and is mirrored for negative inputs
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Curve {
+    /// Leaves inputs unchanged
+    #[default]
+    Linear,
+    /// An exponential curve with a strength (`0.0` is linear, with larger values flattening out low inputs more)
+    Exponential(f32),
+    /// A blend of a cubic and a linear curve, weighted from `0.0` (linear) to `1.0` (fully cubic)
+    Cubic(f32),
+}
+
+impl Curve {
+    /// Applies the curve to a normalised input from `-1.0` to `1.0`
+    pub fn apply(self, input: f32) -> f32 {
+        let input = input.clamp(-1.0, 1.0);
+        match self {
+            Self::Linear => input,
+            Self::Exponential(strength) => {
+                let base = expf(-strength);
+                input * (base + expf((fabsf(input) - 1.0) * strength) * (1.0 - base))
+            },
+            Self::Cubic(weight) => weight * input * input * input + (1.0 - weight) * input,
+        }
+    }
+}
+
+/// The configuration of an [`AxisShaper`] or [`StickShaper`]
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Shaping {
+    /// the normalised deadband (from `0.0` to `1.0`) below which inputs are ignored, with the inputs above it rescaled so the output still starts from `0.0`
+    pub deadband: f32,
+    /// the drive curve applied after the deadband
+    pub curve: Curve,
+    /// the maximum change in output per second (`None` for no limit), which smooths out sudden jerks of the joystick
+    pub slew_rate: Option<f32>,
+}
+
+impl Shaping {
+    /// Checks that the deadband is from `0.0` up to (but not including) `1.0`, that the curve's parameter is in range and that the slew rate (if any) is finite and above `0.0`
+    fn is_valid(&self) -> bool {
+        let curve = match self.curve {
+            Curve::Linear => true,
+            Curve::Exponential(strength) => strength.is_finite() && strength >= 0.0,
+            Curve::Cubic(weight) => (0.0..=1.0).contains(&weight),
+        };
+        let slew_rate = self.slew_rate.is_none_or(|rate| rate.is_finite() && rate > 0.0);
+
+        (0.0..1.0).contains(&self.deadband) && curve && slew_rate
+    }
+
+    /// Removes the deadband from a normalised magnitude from `0.0` to `1.0`, rescaling the rest
+    fn remove_deadband(&self, magnitude: f32) -> f32 {
+        if magnitude <= self.deadband {
+            0.0
+        } else {
+            (magnitude - self.deadband) / (1.0 - self.deadband)
+        }
+    }
+}
+
+/// The slew-rate limiting state of a single output
+#[derive(Debug, Clone, Copy, Default)]
+struct Slew {
+    /// the last output and the time (in milliseconds) it was produced
+    last: Option<(f32, u32)>,
+}
+
+impl Slew {
+    /// Limits how far a target output may move from the last output in the time since it
+    fn limit(&mut self, rate: Option<f32>, target: f32, now: u32) -> f32 {
+        let output = match (rate, self.last) {
+            (Some(rate), Some((last, time))) => {
+                let max_step = rate * now.wrapping_sub(time) as f32 / 1000.0;
+                // unlike `clamp`, this can't panic if the bounds are ever out of order
+                target.min(last + max_step).max(last - max_step)
+            },
+            _ => target,
+        };
+
+        self.last = Some((output, now));
+        output
+    }
+}
+
+/// Shapes a single joystick axis
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AxisShaper {
+    /// the configuration of the shaper
+    shaping: Shaping,
+    /// the slew-rate limiting state of the output
+    slew: Slew,
+}
+
+impl AxisShaper {
+    /// Creates a new shaper for a single joystick axis
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the deadband isn't from `0.0` up to `1.0`, the curve's parameter is out of range, or the slew rate isn't finite and above `0.0`
+    pub fn new(shaping: Shaping) -> Result<Self, PROSErr> {
+        if !shaping.is_valid() {
+            return Err(PROSErr::Invalid);
+        }
+
+        Ok(Self {
+            shaping,
+            slew: Slew::default(),
+        })
+    }
+
+    /// Gets the configuration of the shaper
+    pub fn shaping(&self) -> &Shaping {
+        &self.shaping
+    }
+
+    /// Shapes a raw joystick value into a normalised output from `-1.0` to `1.0`
+    ///
+    /// Meant to be called every iteration of a control loop, as the slew rate is limited by the time between calls
+    pub fn shape(&mut self, raw: i8) -> f32 {
+        self.shape_at(raw, rtos::millis())
+    }
+
+    /// Shapes a raw joystick value taken at a time (in milliseconds) into a normalised output from `-1.0` to `1.0`
+    pub fn shape_at(&mut self, raw: i8, now: u32) -> f32 {
+        let input = normalise(raw);
+        let magnitude = self.shaping.remove_deadband(fabsf(input));
+        let target = self.shaping.curve.apply(copysignf(magnitude, input));
+
+        self.slew.limit(self.shaping.slew_rate, target, now)
+    }
+}
+
+/// Shapes both axes of a joystick together, with a radial deadzone so that diagonal movements aren't distorted
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StickShaper {
+    /// the configuration of the shaper, with the deadband applied to the stick's distance from centre
+    shaping: Shaping,
+    /// the slew-rate limiting state of the x output
+    x: Slew,
+    /// the slew-rate limiting state of the y output
+    y: Slew,
+}
+
+impl StickShaper {
+    /// Creates a new shaper for both axes of a joystick
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if the deadband isn't from `0.0` up to `1.0`, the curve's parameter is out of range, or the slew rate isn't finite and above `0.0`
+    pub fn new(shaping: Shaping) -> Result<Self, PROSErr> {
+        if !shaping.is_valid() {
+            return Err(PROSErr::Invalid);
+        }
+
+        Ok(Self {
+            shaping,
+            x: Slew::default(),
+            y: Slew::default(),
+        })
+    }
+
+    /// Gets the configuration of the shaper
+    pub fn shaping(&self) -> &Shaping {
+        &self.shaping
+    }
+
+    /// Shapes raw x and y joystick values into normalised `(x, y)` outputs from `-1.0` to `1.0`
+    ///
+    /// Meant to be called every iteration of a control loop, as the slew rate is limited by the time between calls
+    pub fn shape(&mut self, x: i8, y: i8) -> (f32, f32) {
+        self.shape_at(x, y, rtos::millis())
+    }
+
+    /// Shapes raw x and y joystick values taken at a time (in milliseconds) into normalised `(x, y)` outputs from `-1.0` to `1.0`
+    pub fn shape_at(&mut self, x: i8, y: i8, now: u32) -> (f32, f32) {
+        let (x, y) = (normalise(x), normalise(y));
+        let distance = sqrtf(x * x + y * y);
+
+        // shape the stick's distance from centre, keeping it's direction
+        let (target_x, target_y) = if distance == 0.0 {
+            (0.0, 0.0)
+        } else {
+            let shaped = self.shaping.curve.apply(self.shaping.remove_deadband(distance.min(1.0)));
+            let scale = shaped / distance;
+            ((x * scale).clamp(-1.0, 1.0), (y * scale).clamp(-1.0, 1.0))
+        };
+
+        (
+            self.x.limit(self.shaping.slew_rate, target_x, now),
+            self.y.limit(self.shaping.slew_rate, target_y, now),
+        )
+    }
+}
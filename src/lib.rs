@@ -1,7 +1,7 @@
 //! A modular, safe and data-orientated rust wrapper over the Purdue PROS library for vex
 
-#![no_std]
-#![feature(alloc_error_handler)]
+#![cfg_attr(not(feature = "sim"), no_std)]
+#![cfg_attr(not(feature = "sim"), feature(alloc_error_handler))]
 #![feature(negative_impls)]
 #![warn(missing_docs)]
 
@@ -9,20 +9,29 @@ pub mod bindings;
 
 extern crate alloc;
 
+#[cfg(not(feature = "sim"))]
 pub mod allocator;
 pub mod entry;
 pub mod rtos;
 pub mod io;
 pub mod error;
 pub mod port;
+pub mod color;
 pub mod motor;
+pub mod motor_group;
 pub mod adi;
+pub mod pneumatics;
 pub mod controller;
+pub mod input;
 pub mod imu;
+pub mod fused_imu;
 pub mod rotation;
 pub mod fs;
+#[cfg(feature = "sim")]
+pub mod sim;
 
 /// Handles the program's panics
+#[cfg(not(feature = "sim"))]
 #[panic_handler]
 fn panic_handler(info: &core::panic::PanicInfo) -> ! {
     crate::io::eprintln!(
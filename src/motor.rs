@@ -1,6 +1,8 @@
 //! # Motor API
 
-use crate::{bindings, error::{PROSErr, PROSResult}, port::SmartPort};
+use core::ops::{BitAnd, BitOr, Not};
+use alloc::{boxed::Box, vec::Vec};
+use crate::{bindings, error::{PROSErr, PROSResult}, port::SmartPort, rtos};
 
 /// Gets the current voltage for a motor from `-12000` to `12000`
 ///
@@ -34,3 +36,440 @@ pub fn move_voltage(port: SmartPort, reversed: bool, val: i32) -> Result<(), PRO
         bindings::motor_move_voltage(port as i8 * if reversed { -1 } else { 1 }, val)
     }.check().map(|_| ())
 }
+
+/// Sets the target absolute position for a motor to move to using it's onboard PID
+///
+/// The position is in the motor's encoder units, relative to the position the motor was at when initialised or last had it's zero position set. The velocity is the maximum velocity of the movement in RPM
+///
+/// **Note:** this only sets the target, it doesn't block until the movement is finished
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn move_absolute(port: SmartPort, reversed: bool, position: f64, velocity: i32) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::motor_move_absolute(port as i8 * if reversed { -1 } else { 1 }, position, velocity)
+    }.check().map(|_| ())
+}
+
+/// Sets the target position for a motor to move to, relative to it's current position, using it's onboard PID
+///
+/// The position is in the motor's encoder units. The velocity is the maximum velocity of the movement in RPM
+///
+/// **Note:** this only sets the target, it doesn't block until the movement is finished
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn move_relative(port: SmartPort, reversed: bool, position: f64, velocity: i32) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::motor_move_relative(port as i8 * if reversed { -1 } else { 1 }, position, velocity)
+    }.check().map(|_| ())
+}
+
+/// Sets the velocity for a motor in RPM, which is held using it's onboard PID
+///
+/// The range of the velocity depends on the motor's gearset: `-100` to `100` for red (36:1), `-200` to `200` for green (18:1) and `-600` to `600` for blue (6:1)
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn move_velocity(port: SmartPort, reversed: bool, velocity: i32) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::motor_move_velocity(port as i8 * if reversed { -1 } else { 1 }, velocity)
+    }.check().map(|_| ())
+}
+
+/// Changes the maximum velocity (in RPM) of a motor's current profiled movement from [`move_absolute`] or [`move_relative`]
+///
+/// This has no effect if the motor isn't currently following a profiled movement
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn modify_profiled_velocity(port: SmartPort, reversed: bool, velocity: i32) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::motor_modify_profiled_velocity(port as i8 * if reversed { -1 } else { 1 }, velocity)
+    }.check().map(|_| ())
+}
+
+/// Gets the target position (in encoder units) last set for a motor by [`move_absolute`] or [`move_relative`]
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn get_target_position(port: SmartPort, reversed: bool) -> Result<f64, PROSErr> {
+    unsafe {
+        bindings::motor_get_target_position(port as i8 * if reversed { -1 } else { 1 })
+    }.check()
+}
+
+/// Gets the velocity (in RPM) last commanded to a motor by [`move_velocity`]
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn get_target_velocity(port: SmartPort, reversed: bool) -> Result<i32, PROSErr> {
+    unsafe {
+        bindings::motor_get_target_velocity(port as i8 * if reversed { -1 } else { 1 })
+    }.check()
+}
+
+/// A snapshot of a motor's health and motion
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Telemetry {
+    /// the temperature of the motor in degrees celsius
+    pub temperature: f64,
+    /// the current drawn by the motor in mA
+    pub current_draw: i32,
+    /// the torque generated by the motor in Nm
+    pub torque: f64,
+    /// the power drawn by the motor in watts
+    pub power: f64,
+    /// the efficiency of the motor in percent (`0` when stalled, `100` when drawing no power)
+    pub efficiency: f64,
+    /// the actual velocity of the motor in RPM
+    pub velocity: f64,
+    /// the absolute position of the motor in encoder units
+    pub position: f64,
+}
+
+/// A field of a motor's [`Telemetry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelemetryField {
+    /// [`Telemetry::temperature`]
+    Temperature,
+    /// [`Telemetry::current_draw`]
+    CurrentDraw,
+    /// [`Telemetry::torque`]
+    Torque,
+    /// [`Telemetry::power`]
+    Power,
+    /// [`Telemetry::efficiency`]
+    Efficiency,
+    /// [`Telemetry::velocity`]
+    Velocity,
+    /// [`Telemetry::position`]
+    Position,
+}
+
+/// An error reading one of the fields of a motor's [`Telemetry`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TelemetryErr {
+    /// the field that failed to be read
+    pub field: TelemetryField,
+    /// the error that occurred while reading it
+    pub err: PROSErr,
+}
+
+/// Checks the value read for a single field of a motor's [`Telemetry`]
+fn check_field<T: PROSResult>(field: TelemetryField, val: T) -> Result<T, TelemetryErr> {
+    val.check().map_err(|err| TelemetryErr { field, err })
+}
+
+/// Reads all of a motor's [`Telemetry`] at once
+///
+/// # Errors
+///
+/// - Returns a `TelemetryErr` naming the first field that failed to be read, which holds `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn telemetry(port: SmartPort, reversed: bool) -> Result<Telemetry, TelemetryErr> {
+    let port = port as i8 * if reversed { -1 } else { 1 };
+
+    unsafe {
+        Ok(Telemetry {
+            temperature: check_field(TelemetryField::Temperature, bindings::motor_get_temperature(port))?,
+            current_draw: check_field(TelemetryField::CurrentDraw, bindings::motor_get_current_draw(port))?,
+            torque: check_field(TelemetryField::Torque, bindings::motor_get_torque(port))?,
+            power: check_field(TelemetryField::Power, bindings::motor_get_power(port))?,
+            efficiency: check_field(TelemetryField::Efficiency, bindings::motor_get_efficiency(port))?,
+            velocity: check_field(TelemetryField::Velocity, bindings::motor_get_actual_velocity(port))?,
+            position: check_field(TelemetryField::Position, bindings::motor_get_position(port))?,
+        })
+    }
+}
+
+/// The gearset (cartridge) installed in a motor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum Gearset {
+    /// 36:1 red cartridge, 100 RPM
+    Red = 0,
+    /// 18:1 green cartridge, 200 RPM
+    Green = 1,
+    /// 6:1 blue cartridge, 600 RPM
+    Blue = 2,
+}
+
+impl TryFrom<u32> for Gearset {
+    type Error = PROSErr;
+
+    fn try_from(raw: u32) -> Result<Self, PROSErr> {
+        match raw {
+            0 => Ok(Self::Red),
+            1 => Ok(Self::Green),
+            2 => Ok(Self::Blue),
+            _ => Err(PROSErr::Invalid),
+        }
+    }
+}
+
+/// How a motor behaves when it's told to stop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum BrakeMode {
+    /// The motor coasts to a stop
+    Coast = 0,
+    /// The motor brakes by shorting it's windings
+    Brake = 1,
+    /// The motor actively holds it's position
+    Hold = 2,
+}
+
+impl TryFrom<u32> for BrakeMode {
+    type Error = PROSErr;
+
+    fn try_from(raw: u32) -> Result<Self, PROSErr> {
+        match raw {
+            0 => Ok(Self::Coast),
+            1 => Ok(Self::Brake),
+            2 => Ok(Self::Hold),
+            _ => Err(PROSErr::Invalid),
+        }
+    }
+}
+
+/// The units a motor's position is measured in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u32)]
+pub enum EncoderUnits {
+    /// Degrees of rotation
+    Degrees = 0,
+    /// Full rotations
+    Rotations = 1,
+    /// Raw encoder ticks
+    Counts = 2,
+}
+
+impl TryFrom<u32> for EncoderUnits {
+    type Error = PROSErr;
+
+    fn try_from(raw: u32) -> Result<Self, PROSErr> {
+        match raw {
+            0 => Ok(Self::Degrees),
+            1 => Ok(Self::Rotations),
+            2 => Ok(Self::Counts),
+            _ => Err(PROSErr::Invalid),
+        }
+    }
+}
+
+/// The configuration of a motor
+///
+/// The default configuration is the one a motor starts with when it's plugged in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Config {
+    /// the gearset installed in the motor
+    pub gearset: Gearset,
+    /// how the motor behaves when it's told to stop
+    pub brake_mode: BrakeMode,
+    /// the units the motor's position is measured in
+    pub encoder_units: EncoderUnits,
+    /// the maximum current the motor may draw in mA
+    pub current_limit: i32,
+    /// the maximum voltage the motor may be driven at in mV
+    pub voltage_limit: i32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            gearset: Gearset::Green,
+            brake_mode: BrakeMode::Coast,
+            encoder_units: EncoderUnits::Degrees,
+            current_limit: 2500,
+            voltage_limit: 12000,
+        }
+    }
+}
+
+/// Applies a full configuration to a motor
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn configure(port: SmartPort, config: Config) -> Result<(), PROSErr> {
+    unsafe {
+        bindings::motor_set_gearing(port as i8, config.gearset as u32).check()?;
+        bindings::motor_set_brake_mode(port as i8, config.brake_mode as u32).check()?;
+        bindings::motor_set_encoder_units(port as i8, config.encoder_units as u32).check()?;
+        bindings::motor_set_current_limit(port as i8, config.current_limit).check()?;
+        bindings::motor_set_voltage_limit(port as i8, config.voltage_limit).check()?;
+    }
+
+    Ok(())
+}
+
+/// Reads back the current configuration of a motor
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+/// - Returns `PROSErr::Invalid` if the motor reports a gearset, brake mode or encoder unit unknown to `safe-vex`
+pub fn get_config(port: SmartPort) -> Result<Config, PROSErr> {
+    unsafe {
+        Ok(Config {
+            gearset: bindings::motor_get_gearing(port as i8).check()?.try_into()?,
+            brake_mode: bindings::motor_get_brake_mode(port as i8).check()?.try_into()?,
+            encoder_units: bindings::motor_get_encoder_units(port as i8).check()?.try_into()?,
+            current_limit: bindings::motor_get_current_limit(port as i8).check()?,
+            voltage_limit: bindings::motor_get_voltage_limit(port as i8).check()?,
+        })
+    }
+}
+
+/// A set of faults raised by a motor
+///
+/// Individual faults are provided as constants and combined with `|`, like bitflags
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MotorFaults(u32);
+
+impl MotorFaults {
+    /// No faults
+    pub const NONE: Self = Self(0x00);
+    /// The motor is over temperature
+    pub const OVER_TEMP: Self = Self(0x01);
+    /// The motor's H-bridge has faulted
+    pub const H_BRIDGE_FAULT: Self = Self(0x02);
+    /// The motor is over current
+    pub const OVER_CURRENT: Self = Self(0x04);
+    /// The motor's H-bridge driver is over current
+    pub const DRIVER_OVER_CURRENT: Self = Self(0x08);
+    /// Every fault
+    pub const ALL: Self = Self(0x0F);
+
+    /// Creates a set of faults from it's raw bits, dropping any bits that aren't known faults
+    pub const fn from_bits_truncate(bits: u32) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    /// Gets the raw bits of the set of faults
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Checks if no faults are raised
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Checks if **all** of the faults in `other` are raised
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl BitOr for MotorFaults {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitAnd for MotorFaults {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl Not for MotorFaults {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+/// Gets the faults currently raised by a motor
+///
+/// # Errors
+///
+/// - Returns `PROSErr::NoDev` if the port cannot be configured as a motor
+pub fn faults(port: SmartPort) -> Result<MotorFaults, PROSErr> {
+    unsafe {
+        bindings::motor_get_faults(port as i8)
+    }.check().map(MotorFaults::from_bits_truncate)
+}
+
+/// A change in the faults of a motor watched by a [`FaultWatchdog`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaultReport {
+    /// the port of the motor
+    pub port: SmartPort,
+    /// the faults newly raised by the motor, or the error from reading it's faults if it newly failed
+    pub faults: Result<MotorFaults, PROSErr>,
+}
+
+/// Periodically scans a set of motors for faults, reporting only the faults that are newly raised
+///
+/// Motors that fail to be read (such as when they are unplugged) are reported once when they start failing. Once they recover, every fault they currently have is reported as newly raised, as there's no telling what was raised while they couldn't be read
+#[derive(Debug, Clone)]
+pub struct FaultWatchdog {
+    /// each watched motor's port and the faults it had (or `None` if it failed to be read) when last scanned
+    motors: Box<[(SmartPort, Option<MotorFaults>)]>,
+    /// the minimum amount of milliseconds between scans
+    interval: u32,
+    /// the time (in milliseconds) of the last scan
+    last_scan: Option<u32>,
+}
+
+impl FaultWatchdog {
+    /// Creates a new watchdog over a set of motors that scans them at most once every `interval` milliseconds
+    pub fn new(ports: &[SmartPort], interval: u32) -> Self {
+        Self {
+            motors: ports.iter().map(|&port| (port, Some(MotorFaults::NONE))).collect(),
+            interval,
+            last_scan: None,
+        }
+    }
+
+    /// Scans the watched motors if at least the watchdog's interval has passed since the last scan, reporting any motors with newly raised faults or newly failed reads
+    ///
+    /// Meant to be called every iteration of a control loop; returns no reports when it's not yet time to scan
+    pub fn poll(&mut self) -> Box<[FaultReport]> {
+        let now = rtos::millis();
+        if self.last_scan.is_some_and(|last| now.wrapping_sub(last) < self.interval) {
+            return Box::new([]);
+        }
+
+        self.scan()
+    }
+
+    /// Scans the watched motors immediately, reporting any motors with newly raised faults or newly failed reads
+    pub fn scan(&mut self) -> Box<[FaultReport]> {
+        self.last_scan = Some(rtos::millis());
+
+        let mut reports = Vec::new();
+        for (port, last) in self.motors.iter_mut() {
+            let current = faults(*port);
+            let report = match (current, *last) {
+                // only report faults that weren't already raised
+                (Ok(current), Some(last)) => Ok(current & !last),
+                // every fault is new to a motor that couldn't be read last scan
+                (Ok(current), None) => Ok(current),
+                // only report a failed read once
+                (Err(_), None) => Ok(MotorFaults::NONE),
+                (Err(err), Some(_)) => Err(err),
+            };
+            *last = current.ok();
+
+            if report != Ok(MotorFaults::NONE) {
+                reports.push(FaultReport { port: *port, faults: report });
+            }
+        }
+
+        reports.into()
+    }
+}
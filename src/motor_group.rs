@@ -0,0 +1,132 @@
+//! # Motor Group API
+
+use alloc::{boxed::Box, vec::Vec};
+use crate::{error::PROSErr, motor::{self, Telemetry, TelemetryErr}, port::SmartPort};
+
+/// The members of a [`MotorGroup`] that failed a command, along with their errors
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MotorGroupErr<E = PROSErr> {
+    /// the port and error of each motor that failed, in the order they are in the group
+    pub failures: Box<[(SmartPort, E)]>,
+}
+
+/// A group of motors that are commanded together, such as one side of a drivetrain
+///
+/// Every command is sent to **every** motor in the group, even if some of them fail, so a single unplugged motor doesn't stop the rest of the group
+#[derive(Debug, Clone)]
+pub struct MotorGroup {
+    /// the port of each motor and if it's reversed
+    motors: Box<[(SmartPort, bool)]>,
+}
+
+impl MotorGroup {
+    /// Creates a new motor group from the port of each motor and if it's reversed
+    pub fn new(motors: &[(SmartPort, bool)]) -> Self {
+        Self {
+            motors: motors.into(),
+        }
+    }
+
+    /// Gets the port of each motor in the group and if it's reversed
+    pub fn motors(&self) -> &[(SmartPort, bool)] {
+        &self.motors
+    }
+
+    /// Runs a command on every motor in the group, collecting the failures
+    fn broadcast(&self, command: impl Fn(SmartPort, bool) -> Result<(), PROSErr>) -> Result<(), MotorGroupErr> {
+        let failures = self.motors
+            .iter()
+            .filter_map(|&(port, reversed)| command(port, reversed).err().map(|err| (port, err)))
+            .collect::<Box<[_]>>();
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(MotorGroupErr { failures })
+        }
+    }
+
+    /// Sets the exact voltage for every motor in the group from `-12000` to `12000`
+    ///
+    /// # Errors
+    ///
+    /// - Returns the motors that failed, with `PROSErr::NoDev` if their port cannot be configured as a motor
+    pub fn move_voltage(&self, val: i32) -> Result<(), MotorGroupErr> {
+        self.broadcast(|port, reversed| motor::move_voltage(port, reversed, val))
+    }
+
+    /// Sets the velocity (in RPM) for every motor in the group, which is held using their onboard PID
+    ///
+    /// # Errors
+    ///
+    /// - Returns the motors that failed, with `PROSErr::NoDev` if their port cannot be configured as a motor
+    pub fn move_velocity(&self, velocity: i32) -> Result<(), MotorGroupErr> {
+        self.broadcast(|port, reversed| motor::move_velocity(port, reversed, velocity))
+    }
+
+    /// Sets the target absolute position (in encoder units) for every motor in the group to move to at a maximum velocity (in RPM) using their onboard PID
+    ///
+    /// **Note:** this only sets the target, it doesn't block until the movement is finished
+    ///
+    /// # Errors
+    ///
+    /// - Returns the motors that failed, with `PROSErr::NoDev` if their port cannot be configured as a motor
+    pub fn move_absolute(&self, position: f64, velocity: i32) -> Result<(), MotorGroupErr> {
+        self.broadcast(|port, reversed| motor::move_absolute(port, reversed, position, velocity))
+    }
+
+    /// Sets the target position (in encoder units) for every motor in the group to move to, relative to their current positions, at a maximum velocity (in RPM) using their onboard PID
+    ///
+    /// **Note:** this only sets the target, it doesn't block until the movement is finished
+    ///
+    /// # Errors
+    ///
+    /// - Returns the motors that failed, with `PROSErr::NoDev` if their port cannot be configured as a motor
+    pub fn move_relative(&self, position: f64, velocity: i32) -> Result<(), MotorGroupErr> {
+        self.broadcast(|port, reversed| motor::move_relative(port, reversed, position, velocity))
+    }
+
+    /// Reads the [`Telemetry`] of each motor in the group, in the order they are in the group
+    pub fn telemetry(&self) -> Box<[(SmartPort, Result<Telemetry, TelemetryErr>)]> {
+        self.motors
+            .iter()
+            .map(|&(port, reversed)| (port, motor::telemetry(port, reversed)))
+            .collect()
+    }
+
+    /// Reads the [`Telemetry`] of every motor in the group and averages it
+    ///
+    /// # Errors
+    ///
+    /// - Returns the motors whose telemetry failed to be read, with the field that failed for each
+    pub fn average_telemetry(&self) -> Result<Telemetry, MotorGroupErr<TelemetryErr>> {
+        let mut readings = Vec::with_capacity(self.motors.len());
+        let mut failures = Vec::new();
+        for (port, telemetry) in self.telemetry() {
+            match telemetry {
+                Ok(telemetry) => readings.push(telemetry),
+                Err(err) => failures.push((port, err)),
+            }
+        }
+
+        if !failures.is_empty() {
+            return Err(MotorGroupErr { failures: failures.into() });
+        }
+
+        // an empty group has nothing to average
+        if readings.is_empty() {
+            return Ok(Telemetry::default());
+        }
+
+        let len = readings.len() as f64;
+        Ok(Telemetry {
+            temperature: readings.iter().map(|x| x.temperature).sum::<f64>() / len,
+            current_draw: readings.iter().map(|x| x.current_draw).sum::<i32>() / readings.len() as i32,
+            torque: readings.iter().map(|x| x.torque).sum::<f64>() / len,
+            power: readings.iter().map(|x| x.power).sum::<f64>() / len,
+            efficiency: readings.iter().map(|x| x.efficiency).sum::<f64>() / len,
+            velocity: readings.iter().map(|x| x.velocity).sum::<f64>() / len,
+            position: readings.iter().map(|x| x.position).sum::<f64>() / len,
+        })
+    }
+}
@@ -0,0 +1,187 @@
+//! # Pneumatics API
+
+use core::f64::consts::PI;
+use libm::{floor, log};
+use crate::{adi::{AdiDigitalOut, AdiErr}, error::PROSErr, port::{AdiPort, AdiSmartPort, OwnedAdiPort}};
+
+/// The air system behind a [`Solenoid`], used to estimate how much air is left
+///
+/// Pressures are gauge pressures in PSI and all lengths are in millimetres
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AirConfig {
+    /// the total volume of the air tanks in millilitres
+    pub tank_volume: f64,
+    /// the pressure the tanks are pumped up to before a match
+    pub start_pressure: f64,
+    /// the lowest pressure the cylinders still actuate reliably at
+    pub min_pressure: f64,
+    /// the bore (inner diameter) of each cylinder
+    pub bore: f64,
+    /// the stroke length of each cylinder
+    pub stroke: f64,
+    /// the amount of cylinders driven by the solenoid
+    pub cylinders: u32,
+    /// the amount of remaining actuations at (or below) which the air is considered low
+    pub warning_actuations: u32,
+}
+
+impl AirConfig {
+    /// Gets the volume of air (in millilitres) used by a single actuation of every cylinder
+    pub fn actuation_volume(&self) -> f64 {
+        let radius = self.bore / 2.0;
+        // mm³ to mL
+        PI * radius * radius * self.stroke * self.cylinders as f64 / 1000.0
+    }
+}
+
+/// A pneumatic solenoid driven by a digital output on an ADI port, either on the brain or on a 3-wire expander
+///
+/// The solenoid keeps track of it's own state and of every actuation, estimating the air left in the tanks with Boyle's law (every extension or retraction fills the cylinders' volume from the tanks)
+#[derive(Debug)]
+pub struct Solenoid {
+    /// the digital output driving the solenoid
+    out: AdiDigitalOut,
+    /// the air system behind the solenoid
+    air: AirConfig,
+    /// if the solenoid is currently extended
+    extended: bool,
+    /// the amount of times the solenoid has changed state
+    actuations: u32,
+    /// the estimated pressure left in the tanks
+    pressure: f64,
+}
+
+impl Solenoid {
+    /// Configures an ADI port as a solenoid and retracts it, assuming it's tanks are full
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::Invalid` if any of the air system's volumes, pressures or lengths aren't finite and above `0`, it drives no cylinders, or the minimum pressure is above the starting pressure
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn new(port: OwnedAdiPort, air: AirConfig) -> Result<Self, AdiErr> {
+        let positive = [air.tank_volume, air.start_pressure, air.min_pressure, air.bore, air.stroke]
+            .into_iter()
+            .all(|value| value.is_finite() && value > 0.0);
+
+        if !positive || air.cylinders == 0 || air.min_pressure > air.start_pressure {
+            return Err(AdiErr { err: PROSErr::Invalid, ports: port });
+        }
+
+        // every actuation must lose some (but not all) of the pressure, or the estimates never reach the minimum
+        let ratio = air.tank_volume / (air.tank_volume + air.actuation_volume());
+        if !(ratio > 0.0 && ratio < 1.0) {
+            return Err(AdiErr { err: PROSErr::Invalid, ports: port });
+        }
+
+        let mut out = AdiDigitalOut::new(port)?;
+        if let Err(err) = out.write(false) {
+            return Err(AdiErr { err, ports: out.into_port() });
+        }
+
+        Ok(Self {
+            out,
+            air,
+            extended: false,
+            actuations: 0,
+            pressure: air.start_pressure,
+        })
+    }
+
+    /// Gets the SmartPort and ADI port of the solenoid
+    pub fn port(&self) -> (AdiSmartPort, AdiPort) {
+        self.out.port()
+    }
+
+    /// Gives back the port of the solenoid, leaving it in it's current state
+    pub fn into_port(self) -> OwnedAdiPort {
+        self.out.into_port()
+    }
+
+    /// Gets the air system behind the solenoid
+    pub fn air(&self) -> &AirConfig {
+        &self.air
+    }
+
+    /// Extends the solenoid's cylinders, which does nothing (and uses no air) if they're already extended
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn extend(&mut self) -> Result<(), PROSErr> {
+        self.set(true)
+    }
+
+    /// Retracts the solenoid's cylinders, which does nothing (and uses no air) if they're already retracted
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn retract(&mut self) -> Result<(), PROSErr> {
+        self.set(false)
+    }
+
+    /// Extends the solenoid's cylinders if they're retracted and retracts them otherwise
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn toggle(&mut self) -> Result<(), PROSErr> {
+        self.set(!self.extended)
+    }
+
+    /// Sets if the solenoid's cylinders are extended, counting an actuation if that changes their state
+    ///
+    /// # Errors
+    ///
+    /// - Returns `PROSErr::NoDev` if there is no 3-wire expander on the SmartPort
+    pub fn set(&mut self, extended: bool) -> Result<(), PROSErr> {
+        self.out.write(extended)?;
+
+        if extended != self.extended {
+            self.extended = extended;
+            self.actuations += 1;
+            self.pressure *= self.pressure_ratio();
+        }
+        Ok(())
+    }
+
+    /// Gets if the solenoid's cylinders are currently extended (from the last state written, without touching the port)
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+
+    /// Gets the amount of times the solenoid has extended or retracted since it was created or it's tanks were refilled
+    pub fn actuations(&self) -> u32 {
+        self.actuations
+    }
+
+    /// Gets the estimated pressure (in PSI) left in the tanks
+    pub fn pressure(&self) -> f64 {
+        self.pressure
+    }
+
+    /// Gets the estimated amount of actuations left before the pressure drops below the minimum working pressure
+    pub fn remaining_actuations(&self) -> u32 {
+        // the (gauge) pressure falls geometrically, so this is the largest `n` with `pressure * ratio^n >= min_pressure`
+        let remaining = floor(log(self.air.min_pressure / self.pressure) / log(self.pressure_ratio()));
+
+        // negative when the pressure is already below the minimum, and the cast saturates at `u32::MAX`
+        remaining.max(0.0) as u32
+    }
+
+    /// Gets if the air is running low, meaning that there are at most `warning_actuations` actuations left
+    pub fn is_low(&self) -> bool {
+        self.remaining_actuations() <= self.air.warning_actuations
+    }
+
+    /// Resets the pressure estimate and actuation count after the tanks have been pumped back up to their starting pressure
+    pub fn refill(&mut self) {
+        self.pressure = self.air.start_pressure;
+        self.actuations = 0;
+    }
+
+    /// Gets the ratio that the (gauge) pressure is multiplied by on each actuation, as the tanks' air expands into the cylinders
+    fn pressure_ratio(&self) -> f64 {
+        self.air.tank_volume / (self.air.tank_volume + self.air.actuation_volume())
+    }
+}
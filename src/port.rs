@@ -1,8 +1,12 @@
 //! All the possible ports on the Vex v5 brain
 
+#[cfg(not(feature = "sim"))]
+use core::sync::atomic::{AtomicU32, Ordering};
+use crate::bindings;
+
 /// A SmartPort on the Vex v5 brain
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum SmartPort {
     One = 1,
@@ -28,9 +32,11 @@ pub enum SmartPort {
     TwentyOne = 21,
 }
 
-/// An ADI port on the brain
+/// An ADI port on the brain or on a 3-wire expander
+///
+/// This only names the port; the ADI handles are created from an [`OwnedAdiPort`] instead
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[repr(u8)]
 pub enum AdiPort {
     A = 1,
@@ -42,3 +48,134 @@ pub enum AdiPort {
     G = 7,
     H = 8,
 }
+
+impl AdiPort {
+    /// Gets the raw ADI port number (`1` to `8`) that PROS addresses the port by
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Self::A => 1,
+            Self::B => 2,
+            Self::C => 3,
+            Self::D => 4,
+            Self::E => 5,
+            Self::F => 6,
+            Self::G => 7,
+            Self::H => 8,
+        }
+    }
+}
+
+/// The SmartPort that a bank of ADI ports is addressed through
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdiSmartPort {
+    /// The ADI ports built into the brain
+    Internal,
+    /// The ADI ports of a 3-wire expander plugged into a SmartPort
+    Expander(SmartPort),
+}
+
+/// The ADI ports built into the brain
+pub const INTERNAL_ADI_PORT: AdiSmartPort = AdiSmartPort::Internal;
+
+impl AdiSmartPort {
+    /// Gets the raw SmartPort number that PROS addresses the bank of ADI ports by
+    pub fn as_u8(self) -> u8 {
+        match self {
+            Self::Internal => bindings::INTERNAL_ADI_PORT as u8,
+            Self::Expander(port) => port as u8,
+        }
+    }
+}
+
+impl From<SmartPort> for AdiSmartPort {
+    fn from(port: SmartPort) -> Self {
+        Self::Expander(port)
+    }
+}
+
+/// Exclusive ownership of a single ADI port, handed out by [`AdiPorts::take`]
+///
+/// Owned ports can't be copied or created any other way, and every ADI handle (such as [`AdiDigitalOut`](crate::adi::AdiDigitalOut)) consumes the ports it's created from, so a port can never be configured as two things at once
+#[derive(Debug, PartialEq, Eq)]
+pub struct OwnedAdiPort {
+    /// the SmartPort the ADI port is addressed through
+    smart_port: AdiSmartPort,
+    /// the ADI port itself
+    port: AdiPort,
+}
+
+impl OwnedAdiPort {
+    /// Gets the SmartPort the ADI port is addressed through
+    pub fn smart_port(&self) -> AdiSmartPort {
+        self.smart_port
+    }
+
+    /// Gets the ADI port itself
+    pub fn port(&self) -> AdiPort {
+        self.port
+    }
+}
+
+/// Every ADI port of the brain or of a 3-wire expander, each of which can only be taken once for the whole program
+#[allow(missing_docs)]
+#[derive(Debug)]
+pub struct AdiPorts {
+    pub a: OwnedAdiPort,
+    pub b: OwnedAdiPort,
+    pub c: OwnedAdiPort,
+    pub d: OwnedAdiPort,
+    pub e: OwnedAdiPort,
+    pub f: OwnedAdiPort,
+    pub g: OwnedAdiPort,
+    pub h: OwnedAdiPort,
+}
+
+impl AdiPorts {
+    /// Takes ownership of every ADI port on the brain ([`INTERNAL_ADI_PORT`]) or on a 3-wire expander plugged into a SmartPort
+    ///
+    /// Returns `None` if the ports have already been taken
+    pub fn take(smart_port: impl Into<AdiSmartPort>) -> Option<Self> {
+        let smart_port = smart_port.into();
+        if !take_bank(smart_port) {
+            return None;
+        }
+
+        let own = |port| OwnedAdiPort { smart_port, port };
+        Some(Self {
+            a: own(AdiPort::A),
+            b: own(AdiPort::B),
+            c: own(AdiPort::C),
+            d: own(AdiPort::D),
+            e: own(AdiPort::E),
+            f: own(AdiPort::F),
+            g: own(AdiPort::G),
+            h: own(AdiPort::H),
+        })
+    }
+}
+
+/// Marks a bank of ADI ports as taken, returning if it wasn't already
+#[cfg(not(feature = "sim"))]
+fn take_bank(smart_port: AdiSmartPort) -> bool {
+    /// A bit for each bank of ADI ports that has been taken, indexed by SmartPort number (with `0` for the brain's own ports)
+    static TAKEN: AtomicU32 = AtomicU32::new(0);
+
+    let bit = 1 << bank_index(smart_port);
+    TAKEN.fetch_or(bit, Ordering::Relaxed) & bit == 0
+}
+
+/// Marks a bank of ADI ports as taken, returning if it wasn't already
+///
+/// Each simulated brain keeps track of it's own banks, so that every test starts with none taken
+#[cfg(feature = "sim")]
+fn take_bank(smart_port: AdiSmartPort) -> bool {
+    crate::sim::take_adi_bank(bank_index(smart_port))
+}
+
+/// Gets the index of a bank of ADI ports, with `0` for the brain's own ports and the SmartPort number otherwise
+fn bank_index(smart_port: AdiSmartPort) -> u32 {
+    match smart_port {
+        AdiSmartPort::Internal => 0,
+        AdiSmartPort::Expander(port) => port as u32,
+    }
+}
@@ -1,5 +1,6 @@
 //! # Rotation Sensor API
 
+use libm::round;
 use crate::{bindings, error::{PROSErr, PROSResult}, port::SmartPort};
 
 /// Resets the Rotation Sensor
@@ -28,14 +29,15 @@ pub fn set_data_rate(port: SmartPort, rate: u32) -> Result<(), PROSErr> {
     }.check().map(|_| ())
 }
 
-/// Sets the Rotation Sensor position reading to a desired rotation value
+/// Sets the Rotation Sensor position reading to a desired rotation value in centidegrees, which may be negative
 ///
 /// # Errors
 /// 
 /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
-pub fn set_position(port: SmartPort, position: u32) -> Result<(), PROSErr> {
+pub fn set_position(port: SmartPort, position: i32) -> Result<(), PROSErr> {
     unsafe {
-        bindings::rotation_set_position(port as u8, position)
+        // PROS takes the position as a `uint32_t` but stores it signed, so the bits are passed through as-is
+        bindings::rotation_set_position(port as u8, position as u32)
     }.check().map(|_| ())
 }
 
@@ -104,3 +106,205 @@ pub fn get_reversed(port: SmartPort) -> Result<bool, PROSErr> {
         bindings::rotation_get_reversed(port as u8)
     }.check().map(|x| x != 0)
 }
+
+/// An angle, such as a Rotation Sensor's position or absolute angle
+///
+/// The angle can only be created and read through it's unit-named methods, so units can't be mixed up
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct Angle(
+    /// the angle in degrees
+    f64,
+);
+
+impl Angle {
+    /// Creates an angle from degrees
+    pub const fn from_degrees(degrees: f64) -> Self {
+        Self(degrees)
+    }
+
+    /// Creates an angle from radians
+    pub fn from_radians(radians: f64) -> Self {
+        Self(radians.to_degrees())
+    }
+
+    /// Creates an angle from whole revolutions
+    pub fn from_revolutions(revolutions: f64) -> Self {
+        Self(revolutions * 360.0)
+    }
+
+    /// Creates an angle from the centidegrees used by PROS
+    fn from_centidegrees(centidegrees: i32) -> Self {
+        Self(centidegrees as f64 / 100.0)
+    }
+
+    /// Gets the angle in degrees
+    pub const fn degrees(&self) -> f64 {
+        self.0
+    }
+
+    /// Gets the angle in radians
+    pub fn radians(&self) -> f64 {
+        self.0.to_radians()
+    }
+
+    /// Gets the angle in whole revolutions
+    pub fn revolutions(&self) -> f64 {
+        self.0 / 360.0
+    }
+
+    /// Gets the angle in the centidegrees used by PROS, rounded to the nearest centidegree
+    fn centidegrees(&self) -> i32 {
+        round(self.0 * 100.0) as i32
+    }
+}
+
+/// An angular velocity, such as a Rotation Sensor's velocity
+///
+/// The velocity can only be created and read through it's unit-named methods, so units can't be mixed up
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd)]
+pub struct AngularVelocity(
+    /// the angular velocity in degrees per second
+    f64,
+);
+
+impl AngularVelocity {
+    /// Creates an angular velocity from degrees per second
+    pub const fn from_degrees_per_sec(degrees_per_sec: f64) -> Self {
+        Self(degrees_per_sec)
+    }
+
+    /// Creates an angular velocity from radians per second
+    pub fn from_radians_per_sec(radians_per_sec: f64) -> Self {
+        Self(radians_per_sec.to_degrees())
+    }
+
+    /// Creates an angular velocity from revolutions per minute
+    pub fn from_rpm(rpm: f64) -> Self {
+        Self(rpm * 6.0)
+    }
+
+    /// Creates an angular velocity from the centidegrees per second used by PROS
+    fn from_centidegrees_per_sec(centidegrees_per_sec: i32) -> Self {
+        Self(centidegrees_per_sec as f64 / 100.0)
+    }
+
+    /// Gets the angular velocity in degrees per second
+    pub const fn degrees_per_sec(&self) -> f64 {
+        self.0
+    }
+
+    /// Gets the angular velocity in radians per second
+    pub fn radians_per_sec(&self) -> f64 {
+        self.0.to_radians()
+    }
+
+    /// Gets the angular velocity in revolutions per minute
+    pub fn rpm(&self) -> f64 {
+        self.0 / 6.0
+    }
+}
+
+/// A Rotation Sensor on a SmartPort, reading in typed units rather than raw centidegrees
+#[derive(Debug)]
+pub struct RotationSensor {
+    /// the port of the sensor
+    port: SmartPort,
+    /// if the sensor counts clockwise rotations as negative
+    reversed: bool,
+}
+
+impl RotationSensor {
+    /// Creates a handle to the Rotation Sensor on a SmartPort, setting it's reversed flag
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn new(port: SmartPort, reversed: bool) -> Result<Self, PROSErr> {
+        set_reversed(port, reversed)?;
+        Ok(Self { port, reversed })
+    }
+
+    /// Gets the port of the sensor
+    pub fn port(&self) -> SmartPort {
+        self.port
+    }
+
+    /// Gets if the sensor is reversed (from the last flag set, without touching the port)
+    pub fn is_reversed(&self) -> bool {
+        self.reversed
+    }
+
+    /// Sets if the sensor is reversed, so that clockwise rotations count as negative
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn set_reversed(&mut self, reversed: bool) -> Result<(), PROSErr> {
+        set_reversed(self.port, reversed)?;
+        self.reversed = reversed;
+        Ok(())
+    }
+
+    /// Gets the sensor's cumulative position, which keeps counting past a full turn
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn position(&self) -> Result<Angle, PROSErr> {
+        get_position(self.port).map(Angle::from_centidegrees)
+    }
+
+    /// Sets the sensor's cumulative position, which may be negative, rounded to the nearest centidegree
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn set_position(&mut self, position: Angle) -> Result<(), PROSErr> {
+        set_position(self.port, position.centidegrees())
+    }
+
+    /// Resets the sensor's cumulative position to zero
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn reset_position(&mut self) -> Result<(), PROSErr> {
+        reset_position(self.port)
+    }
+
+    /// Resets the sensor's cumulative position to it's absolute angle
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn reset(&mut self) -> Result<(), PROSErr> {
+        reset(self.port)
+    }
+
+    /// Gets the sensor's absolute angle, bounded by `0..360` degrees
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn angle(&self) -> Result<Angle, PROSErr> {
+        get_angle(self.port).map(Angle::from_centidegrees)
+    }
+
+    /// Gets the sensor's velocity
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn velocity(&self) -> Result<AngularVelocity, PROSErr> {
+        get_velocity(self.port).map(AngularVelocity::from_centidegrees_per_sec)
+    }
+
+    /// Sets the sensor's refresh interval in milliseconds, rounded down to an increment of 5ms (with a minimum of 5ms)
+    ///
+    /// # Errors
+    ///
+    /// Returns `PROSErr::NoDev` if the port cannot be configured as a Rotation Sensor
+    pub fn set_data_rate(&mut self, rate: u32) -> Result<(), PROSErr> {
+        set_data_rate(self.port, rate)
+    }
+}
@@ -0,0 +1,126 @@
+//! Simulated ADI (3-wire) ports, both on the brain and on 3-wire expanders
+
+use alloc::{boxed::Box, vec::Vec};
+use crate::{color::Rgb, port::{AdiPort, AdiSmartPort, SmartPort}};
+use super::{Device, SmartDevice};
+
+/// The amount of ADI ports on the brain or a 3-wire expander
+pub(crate) const ADI_PORTS: usize = 8;
+
+/// `E_ADI_TYPE_UNDEFINED`, the configuration of an ADI port that hasn't been configured yet
+pub(crate) const TYPE_UNDEFINED: u32 = 255;
+
+/// The state of a single simulated ADI port
+#[derive(Debug, Clone)]
+pub(crate) struct AdiPortState {
+    /// the raw `adi_port_config_e_t` the port is configured as
+    pub config: u32,
+    /// the raw value last written to, or scripted onto, the port
+    pub value: i32,
+    /// the calibration value of an analog input
+    pub calibration: i32,
+    /// if a sensor spanning this port counts in the opposite direction
+    pub reversed: bool,
+    /// the multiplier applied to the readings of a gyro
+    pub multiplier: f64,
+    /// the colours last sent to an LED strip, in `0xRRGGBB` form
+    pub pixels: Vec<u32>,
+}
+
+impl Default for AdiPortState {
+    fn default() -> Self {
+        Self {
+            config: TYPE_UNDEFINED,
+            value: 0,
+            calibration: 0,
+            reversed: false,
+            multiplier: 1.0,
+            pixels: Vec::new(),
+        }
+    }
+}
+
+/// The state of a bank of simulated ADI ports
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Adi {
+    /// each of the ADI ports from `A` to `H`
+    pub ports: [AdiPortState; ADI_PORTS],
+}
+
+impl SmartDevice for Adi {
+    fn from_device(device: &mut Device) -> Option<&mut Self> {
+        match device {
+            Device::AdiExpander(adi) => Some(adi),
+            _ => None,
+        }
+    }
+}
+
+/// Runs a closure on a single port of the brain's ADI ports or of the 3-wire expander on a SmartPort
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+fn with_port<R>(smart_port: impl Into<AdiSmartPort>, port: AdiPort, f: impl FnOnce(&mut AdiPortState) -> R) -> Option<R> {
+    let index = port.as_u8() as usize - 1;
+    match smart_port.into() {
+        AdiSmartPort::Internal => Some(super::with(|brain| f(&mut brain.adi.ports[index]))),
+        AdiSmartPort::Expander(smart_port) => super::with_device(smart_port, |adi: &mut Adi| f(&mut adi.ports[index])),
+    }
+}
+
+/// Plugs a new 3-wire expander, with all of it's ADI ports unconfigured, into a SmartPort, replacing whatever was plugged in before
+pub fn plug_expander(port: SmartPort) {
+    super::with(|brain| brain.ports[port as usize - 1] = Some(Device::AdiExpander(Box::default())));
+}
+
+/// Gets the raw configuration (`adi_port_config_e_t`) of an ADI port
+///
+/// Ports that haven't been configured yet are `255` (`E_ADI_TYPE_UNDEFINED`)
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn config(smart_port: impl Into<AdiSmartPort>, port: AdiPort) -> Option<u32> {
+    with_port(smart_port, port, |state| state.config)
+}
+
+/// Gets the raw value last written to an ADI port
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn value(smart_port: impl Into<AdiSmartPort>, port: AdiPort) -> Option<i32> {
+    with_port(smart_port, port, |state| state.value)
+}
+
+/// Sets the raw value that reads from an ADI port will see
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn set_value(smart_port: impl Into<AdiSmartPort>, port: AdiPort, value: i32) -> Option<()> {
+    with_port(smart_port, port, |state| state.value = value)
+}
+
+/// Turns the legacy encoder whose top wire is in an ADI port by a number of ticks (`360` per revolution)
+///
+/// The encoder counts in the opposite direction if it was initialised as reversed
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn turn_encoder(smart_port: impl Into<AdiSmartPort>, top: AdiPort, ticks: i32) -> Option<()> {
+    with_port(smart_port, top, |state| state.value += if state.reversed { -ticks } else { ticks })
+}
+
+/// Sets the distance (in millimetres) that the legacy ultrasonic rangefinder whose ping wire is in an ADI port measures, with `None` meaning no echo is heard
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn set_ultrasonic(smart_port: impl Into<AdiSmartPort>, ping: AdiPort, distance: Option<i32>) -> Option<()> {
+    with_port(smart_port, ping, |state| state.value = distance.map_or(0, |distance| distance * 10))
+}
+
+/// Turns the legacy gyro in an ADI port by a number of degrees
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn turn_gyro(smart_port: impl Into<AdiSmartPort>, port: AdiPort, degrees: f64) -> Option<()> {
+    with_port(smart_port, port, |state| state.value += (degrees * 10.0) as i32)
+}
+
+/// Gets the colours last sent to the LED strip in an ADI port, which is empty if nothing has been sent yet
+///
+/// Returns `None` if there is no 3-wire expander on the SmartPort
+pub fn led_strip(smart_port: impl Into<AdiSmartPort>, port: AdiPort) -> Option<Vec<Rgb>> {
+    with_port(smart_port, port, |state| state.pixels.iter().copied().map(Rgb::from_u32).collect())
+}
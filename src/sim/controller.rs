@@ -0,0 +1,100 @@
+//! Simulated V5 controllers
+
+use alloc::string::String;
+use crate::controller::{Controller, ControllerAnalog, ControllerDigital, LCD_COLUMNS, LCD_LINES};
+
+/// The state of a simulated controller
+#[derive(Debug, Clone)]
+pub(crate) struct ControllerDevice {
+    /// if the controller is connected to the brain
+    pub connected: bool,
+    /// the position of each joystick axis from `-127` to `127`
+    pub analog: [i32; 4],
+    /// if each button is held down, starting from `L1`
+    pub digital: [bool; 12],
+    /// if each button was held down the last time it was checked for a new press
+    pub last_press: [bool; 12],
+    /// the last rumble pattern sent to the controller
+    pub rumble: Option<String>,
+    /// the battery level the controller reports
+    pub battery_level: i32,
+    /// the battery capacity the controller reports
+    pub battery_capacity: i32,
+    /// the characters shown on each line of the controller's screen
+    pub screen: [[char; LCD_COLUMNS as usize]; LCD_LINES as usize],
+    /// the time (in microseconds) the screen was last updated
+    pub last_screen_update: Option<u64>,
+}
+
+impl Default for ControllerDevice {
+    fn default() -> Self {
+        Self {
+            connected: false,
+            analog: [0; 4],
+            digital: [false; 12],
+            last_press: [false; 12],
+            rumble: None,
+            battery_level: 100,
+            battery_capacity: 100,
+            screen: [[' '; LCD_COLUMNS as usize]; LCD_LINES as usize],
+            last_screen_update: None,
+        }
+    }
+}
+
+impl ControllerDevice {
+    /// Creates a new connected controller
+    pub fn connected() -> Self {
+        Self {
+            connected: true,
+            ..Self::default()
+        }
+    }
+}
+
+/// Connects a controller to the brain
+///
+/// The master controller starts off connected and the partner controller starts off disconnected
+pub fn connect(controller: Controller) {
+    super::with(|brain| brain.controllers[controller as usize].connected = true);
+}
+
+/// Disconnects a controller from the brain
+pub fn disconnect(controller: Controller) {
+    super::with(|brain| brain.controllers[controller as usize].connected = false);
+}
+
+/// Moves a joystick axis on a controller
+pub fn set_analog(controller: Controller, analog: ControllerAnalog, val: i8) {
+    super::with(|brain| brain.controllers[controller as usize].analog[analog as usize] = val as i32);
+}
+
+/// Presses (`true`) or releases (`false`) a button on a controller
+pub fn set_digital(controller: Controller, digital: ControllerDigital, pressed: bool) {
+    super::with(|brain| brain.controllers[controller as usize].digital[digital as usize - ControllerDigital::L1 as usize] = pressed);
+}
+
+/// Sets the battery level and capacity that a controller reports, which both start off at `100`
+pub fn set_battery(controller: Controller, level: i32, capacity: i32) {
+    super::with(|brain| {
+        let controller = &mut brain.controllers[controller as usize];
+        controller.battery_level = level;
+        controller.battery_capacity = capacity;
+    });
+}
+
+/// Gets the last rumble pattern sent to a controller
+pub fn last_rumble(controller: Controller) -> Option<String> {
+    super::with(|brain| brain.controllers[controller as usize].rumble.clone())
+}
+
+/// Gets the text shown on each line of a controller's screen, without trailing spaces
+///
+/// Like on a real controller, screen updates sent less than `50`ms after the last one are dropped
+pub fn screen(controller: Controller) -> [String; LCD_LINES as usize] {
+    super::with(|brain| {
+        brain.controllers[controller as usize]
+            .screen
+            .map(|line| String::from_iter(line).trim_end().into())
+    })
+}
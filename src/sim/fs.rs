@@ -0,0 +1,44 @@
+//! Simulated SD card
+
+use alloc::string::{String, ToString};
+use std::collections::BTreeMap;
+
+/// A file opened through the simulated `fopen`
+#[derive(Debug, Clone)]
+pub(crate) struct OpenFile {
+    /// the full path of the file (starting with `/usd/`)
+    pub path: String,
+    /// if the file was opened for writing
+    pub writable: bool,
+    /// the byte offset that the next read starts from
+    pub position: usize,
+    /// the stream's error indicator, set when a read or write fails
+    pub error: bool,
+}
+
+/// Inserts an empty SD card into the brain, replacing any card that was inserted before
+pub fn insert_card() {
+    super::with(|brain| brain.usd = Some(BTreeMap::new()));
+}
+
+/// Removes the SD card from the brain, losing all of its files
+pub fn remove_card() {
+    super::with(|brain| brain.usd = None);
+}
+
+/// Reads the contents of a file (path starting with `/usd/`) on the SD card
+///
+/// Returns `None` if there is no SD card or no such file
+pub fn read(path: &str) -> Option<String> {
+    super::with(|brain| brain.usd.as_ref()?.get(path).cloned())
+}
+
+/// Writes a file (path starting with `/usd/`) to the SD card, replacing its contents if it already exists
+///
+/// Returns `None` if there is no SD card
+pub fn write(path: &str, contents: &str) -> Option<()> {
+    super::with(|brain| {
+        brain.usd.as_mut()?.insert(path.to_string(), contents.to_string());
+        Some(())
+    })
+}
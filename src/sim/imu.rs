@@ -0,0 +1,154 @@
+//! Simulated V5 inertial sensors
+
+use crate::port::SmartPort;
+use super::{Device, SmartDevice};
+
+/// How long a simulated inertial sensor takes to calibrate in milliseconds
+pub const CALIBRATION_TIME: u32 = 2000;
+
+/// The offsets applied to the raw readings of an inertial sensor by taring or setting them
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Offsets {
+    pub heading: f64,
+    pub rotation: f64,
+    pub pitch: f64,
+    pub roll: f64,
+    pub yaw: f64,
+}
+
+/// The state of a simulated inertial sensor
+#[derive(Debug, Clone)]
+pub(crate) struct Imu {
+    /// the time (in milliseconds) the current calibration finishes at, if calibrating
+    pub calibrating_until: Option<u32>,
+    /// the raw cumulative rotation about the z axis in degrees
+    pub rotation: f64,
+    /// the rate (in degrees per second) that the rotation drifts at on it's own
+    pub drift: f64,
+    /// the time (in milliseconds) the rotation last drifted up to
+    pub drifted_at: u32,
+    /// the raw pitch in degrees
+    pub pitch: f64,
+    /// the raw roll in degrees
+    pub roll: f64,
+    /// the raw yaw in degrees
+    pub yaw: f64,
+    /// the raw accelerometer values in g
+    pub accel: [f64; 3],
+    /// the raw gyroscope values in degrees per second
+    pub gyro_rate: [f64; 3],
+    /// the offsets applied on top of the raw readings
+    pub offsets: Offsets,
+    /// the refresh interval in milliseconds
+    pub data_rate: u32,
+}
+
+impl Default for Imu {
+    fn default() -> Self {
+        Self {
+            calibrating_until: None,
+            rotation: 0.0,
+            drift: 0.0,
+            drifted_at: 0,
+            pitch: 0.0,
+            roll: 0.0,
+            yaw: 0.0,
+            accel: [0.0; 3],
+            gyro_rate: [0.0; 3],
+            offsets: Offsets::default(),
+            data_rate: 10,
+        }
+    }
+}
+
+impl Imu {
+    /// Checks if the sensor is still calibrating at a given time in milliseconds
+    pub fn is_calibrating(&self, millis: u32) -> bool {
+        // compared relative to now, as the millisecond counter wraps around
+        self.calibrating_until.is_some_and(|until| (until.wrapping_sub(millis) as i32) > 0)
+    }
+
+    /// Drifts the rotation up to a given time in milliseconds
+    pub fn drift_to(&mut self, millis: u32) {
+        self.rotation += self.drift * millis.wrapping_sub(self.drifted_at) as f64 / 1000.0;
+        self.drifted_at = millis;
+    }
+}
+
+impl SmartDevice for Imu {
+    fn from_device(device: &mut Device) -> Option<&mut Self> {
+        match device {
+            Device::Imu(imu) => Some(imu),
+            _ => None,
+        }
+    }
+}
+
+/// Gets the current simulated time in milliseconds
+fn now() -> u32 {
+    super::with(|brain| (brain.micros / 1000) as u32)
+}
+
+/// Plugs a new, already calibrated inertial sensor into a SmartPort, replacing whatever was plugged in before
+pub fn plug(port: SmartPort) {
+    super::with(|brain| brain.ports[port as usize - 1] = Some(Device::Imu(Imu::default())));
+}
+
+/// Sets the raw cumulative rotation (in degrees) about the z axis that the inertial sensor on a SmartPort measures
+///
+/// Both the sensor's heading and rotation are derived from this value
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn set_rotation(port: SmartPort, degrees: f64) -> Option<()> {
+    let millis = now();
+    super::with_device(port, |imu: &mut Imu| {
+        imu.rotation = degrees;
+        imu.drifted_at = millis;
+    })
+}
+
+/// Makes the raw cumulative rotation of the inertial sensor on a SmartPort drift by a number of degrees every second (in the same clockwise-positive direction as the rotation), like a real sensor does while sitting still
+///
+/// The drift is independent of the gyroscope values set by [`set_gyro_rate`]
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn set_drift(port: SmartPort, degrees_per_sec: f64) -> Option<()> {
+    let millis = now();
+    super::with_device(port, |imu: &mut Imu| {
+        // the rotation drifts up to now at the old rate first
+        imu.drift_to(millis);
+        imu.drift = degrees_per_sec;
+    })
+}
+
+/// Sets the raw pitch, roll and yaw (in degrees) that the inertial sensor on a SmartPort measures
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn set_attitude(port: SmartPort, pitch: f64, roll: f64, yaw: f64) -> Option<()> {
+    super::with_device(port, |imu: &mut Imu| {
+        imu.pitch = pitch;
+        imu.roll = roll;
+        imu.yaw = yaw;
+    })
+}
+
+/// Sets the raw accelerometer values (in g) that the inertial sensor on a SmartPort measures
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn set_accel(port: SmartPort, x: f64, y: f64, z: f64) -> Option<()> {
+    super::with_device(port, |imu: &mut Imu| imu.accel = [x, y, z])
+}
+
+/// Sets the raw gyroscope values (in degrees per second) that the inertial sensor on a SmartPort measures
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn set_gyro_rate(port: SmartPort, x: f64, y: f64, z: f64) -> Option<()> {
+    super::with_device(port, |imu: &mut Imu| imu.gyro_rate = [x, y, z])
+}
+
+/// Gets the refresh interval (in milliseconds) of the inertial sensor on a SmartPort
+///
+/// Returns `None` if there is no inertial sensor on the port
+pub fn data_rate(port: SmartPort) -> Option<u32> {
+    super::with_device(port, |imu: &mut Imu| imu.data_rate)
+}
@@ -0,0 +1,257 @@
+//! Simulated `pros/ext_adi.h`
+//!
+//! The brain's own ADI ports are addressed through [`INTERNAL_ADI_PORT`], exactly like PROS does
+
+use crate::{error::PROSErr, sim::adi::{Adi, AdiPortState, ADI_PORTS}};
+use super::{claim, fail, INTERNAL_ADI_PORT, PROS_ERR_, PROS_ERR_F_, PROS_SUCCESS};
+
+/// `E_ADI_ANALOG_IN`
+const ANALOG_IN: u32 = 0;
+/// `E_ADI_DIGITAL_IN`
+const DIGITAL_IN: u32 = 2;
+/// `E_ADI_DIGITAL_OUT`
+const DIGITAL_OUT: u32 = 3;
+/// `E_ADI_LEGACY_GYRO`
+const LEGACY_GYRO: u32 = 10;
+/// `E_ADI_LEGACY_ENCODER`
+const LEGACY_ENCODER: u32 = 14;
+/// `E_ADI_LEGACY_ULTRASONIC`
+const LEGACY_ULTRASONIC: u32 = 15;
+
+/// Converts an ADI port, given as either `1..=8` or the letters `a..=h` (in any case) like PROS accepts, into an index
+fn port_index(port: u8) -> Option<usize> {
+    match port {
+        b'a'..=b'h' => Some((port - b'a') as usize),
+        b'A'..=b'H' => Some((port - b'A') as usize),
+        1..=8 => Some(port as usize - 1),
+        _ => None,
+    }
+}
+
+/// Merges a SmartPort and ADI port index into a device handle the same way PROS does
+fn merge_ports(smart_port: u8, index: usize) -> i32 {
+    ((smart_port as i32) << 8) | (index as i32 + 1)
+}
+
+/// Splits a device handle back into the SmartPort and ADI port it was created on
+fn split_handle(handle: i32) -> (u8, u8) {
+    ((handle >> 8) as u8, (handle & 0xFF) as u8)
+}
+
+/// Runs a closure on the brain's ADI ports or the 3-wire expander on a SmartPort, failing with `ENXIO` if the SmartPort is out of range and `ENODEV` if no expander is plugged into it
+fn bank<R>(smart_port: u8, sentinel: R, f: impl FnOnce(&mut Adi) -> R) -> R {
+    if smart_port as u32 == INTERNAL_ADI_PORT {
+        crate::sim::with(|brain| f(&mut brain.adi))
+    } else {
+        claim(smart_port, sentinel, |adi, _| f(adi))
+    }
+}
+
+/// Runs a closure on a single ADI port, failing with `ENXIO` if either port is out of range
+fn with_adi_port<R>(smart_port: u8, port: u8, sentinel: R, f: impl FnOnce(&mut AdiPortState) -> R) -> R {
+    let Some(index) = port_index(port) else {
+        return fail(PROSErr::NXIO, sentinel);
+    };
+    debug_assert!(index < ADI_PORTS);
+
+    bank(smart_port, sentinel, |adi| f(&mut adi.ports[index]))
+}
+
+/// Runs a closure on a single ADI port only if it is configured as expected, failing with `EADDRINUSE` otherwise
+fn adi_typed<R: Copy>(smart_port: u8, port: u8, config: u32, sentinel: R, f: impl FnOnce(&mut AdiPortState) -> R) -> R {
+    with_adi_port(smart_port, port, sentinel, |state| {
+        if state.config == config {
+            f(state)
+        } else {
+            fail(PROSErr::AddrInUse, sentinel)
+        }
+    })
+}
+
+/// Runs a closure on the ADI port of a device handle only if it is configured as expected
+fn adi_handle<R: Copy>(handle: i32, config: u32, sentinel: R, f: impl FnOnce(&mut AdiPortState) -> R) -> R {
+    let (smart_port, port) = split_handle(handle);
+    adi_typed(smart_port, port, config, sentinel, f)
+}
+
+/// Validates a pair of ADI ports for a two-wire sensor, returning the index of the first port
+fn port_pair(first: u8, second: u8) -> Result<usize, PROSErr> {
+    let (Some(first), Some(second)) = (port_index(first), port_index(second)) else {
+        return Err(PROSErr::NXIO);
+    };
+
+    // the first port must be `A`, `C`, `E` or `G` with the second port directly after it
+    if first % 2 != 0 || second != first + 1 {
+        return Err(PROSErr::Invalid);
+    }
+    Ok(first)
+}
+
+/// Configures a validated pair of ADI ports for a two-wire sensor, returning it's device handle
+fn init_pair(smart_port: u8, first: u8, second: u8, config: u32, reversed: bool) -> i32 {
+    let first = match port_pair(first, second) {
+        Ok(first) => first,
+        Err(err) => return fail(err, PROS_ERR_),
+    };
+
+    bank(smart_port, PROS_ERR_, |adi| {
+        let ports = &mut adi.ports[first..=first + 1];
+        for state in ports.iter_mut() {
+            *state = AdiPortState { config, ..AdiPortState::default() };
+        }
+        ports[0].reversed = reversed;
+        merge_ports(smart_port, first)
+    })
+}
+
+/// Unconfigures the pair of ADI ports of a two-wire sensor's device handle
+fn shutdown_pair(handle: i32, config: u32) -> i32 {
+    let (smart_port, port) = split_handle(handle);
+    let Some(first) = port_index(port) else {
+        return fail(PROSErr::NXIO, PROS_ERR_);
+    };
+
+    bank(smart_port, PROS_ERR_, |adi| {
+        if adi.ports[first].config != config {
+            return fail(PROSErr::AddrInUse, PROS_ERR_);
+        }
+
+        for state in &mut adi.ports[first..=first + 1] {
+            *state = AdiPortState::default();
+        }
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_port_set_config(smart_port: u8, adi_port: u8, type_: u32) -> i32 {
+    with_adi_port(smart_port, adi_port, PROS_ERR_, |state| {
+        state.config = type_;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_digital_write(smart_port: u8, adi_port: u8, value: bool) -> i32 {
+    adi_typed(smart_port, adi_port, DIGITAL_OUT, PROS_ERR_, |state| {
+        state.value = value as i32;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_digital_read(smart_port: u8, adi_port: u8) -> i32 {
+    adi_typed(smart_port, adi_port, DIGITAL_IN, PROS_ERR_, |state| (state.value != 0) as i32)
+}
+
+pub unsafe fn ext_adi_analog_calibrate(smart_port: u8, adi_port: u8) -> i32 {
+    let calibration = adi_typed(smart_port, adi_port, ANALOG_IN, PROS_ERR_, |state| {
+        state.calibration = state.value;
+        state.calibration
+    });
+
+    // calibration samples the sensor for half a second
+    if calibration != PROS_ERR_ {
+        crate::sim::advance(500);
+    }
+    calibration
+}
+
+pub unsafe fn ext_adi_analog_read(smart_port: u8, adi_port: u8) -> i32 {
+    adi_typed(smart_port, adi_port, ANALOG_IN, PROS_ERR_, |state| state.value)
+}
+
+pub unsafe fn ext_adi_analog_read_calibrated(smart_port: u8, adi_port: u8) -> i32 {
+    adi_typed(smart_port, adi_port, ANALOG_IN, PROS_ERR_, |state| state.value - state.calibration)
+}
+
+pub unsafe fn ext_adi_analog_read_calibrated_HR(smart_port: u8, adi_port: u8) -> i32 {
+    adi_typed(smart_port, adi_port, ANALOG_IN, PROS_ERR_, |state| (state.value - state.calibration) * 16)
+}
+
+pub unsafe fn ext_adi_encoder_init(smart_port: u8, adi_port_top: u8, adi_port_bottom: u8, reverse: bool) -> i32 {
+    init_pair(smart_port, adi_port_top, adi_port_bottom, LEGACY_ENCODER, reverse)
+}
+
+pub unsafe fn ext_adi_encoder_get(enc: i32) -> i32 {
+    adi_handle(enc, LEGACY_ENCODER, PROS_ERR_, |state| state.value)
+}
+
+pub unsafe fn ext_adi_encoder_reset(enc: i32) -> i32 {
+    adi_handle(enc, LEGACY_ENCODER, PROS_ERR_, |state| {
+        state.value = 0;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_encoder_shutdown(enc: i32) -> i32 {
+    shutdown_pair(enc, LEGACY_ENCODER)
+}
+
+pub unsafe fn ext_adi_ultrasonic_init(smart_port: u8, adi_port_ping: u8, adi_port_echo: u8) -> i32 {
+    init_pair(smart_port, adi_port_ping, adi_port_echo, LEGACY_ULTRASONIC, false)
+}
+
+pub unsafe fn ext_adi_ultrasonic_get(ult: i32) -> i32 {
+    adi_handle(ult, LEGACY_ULTRASONIC, PROS_ERR_, |state| state.value)
+}
+
+pub unsafe fn ext_adi_ultrasonic_shutdown(ult: i32) -> i32 {
+    shutdown_pair(ult, LEGACY_ULTRASONIC)
+}
+
+pub unsafe fn ext_adi_gyro_init(smart_port: u8, adi_port: u8, multiplier: f64) -> i32 {
+    let calibrate = with_adi_port(smart_port, adi_port, None, |state| {
+        let calibrate = state.config != LEGACY_GYRO;
+        if calibrate {
+            *state = AdiPortState { config: LEGACY_GYRO, ..AdiPortState::default() };
+        }
+        state.multiplier = multiplier;
+        Some(calibrate)
+    });
+
+    match calibrate {
+        Some(calibrate) => {
+            // gyros calibrate for 1300ms when first configured
+            if calibrate {
+                crate::sim::advance(1300);
+            }
+            merge_ports(smart_port, port_index(adi_port).unwrap_or_default())
+        },
+        None => PROS_ERR_,
+    }
+}
+
+pub unsafe fn ext_adi_gyro_get(gyro: i32) -> f64 {
+    adi_handle(gyro, LEGACY_GYRO, PROS_ERR_F_, |state| state.value as f64 * state.multiplier)
+}
+
+pub unsafe fn ext_adi_gyro_reset(gyro: i32) -> i32 {
+    adi_handle(gyro, LEGACY_GYRO, PROS_ERR_, |state| {
+        state.value = 0;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_gyro_shutdown(gyro: i32) -> i32 {
+    adi_handle(gyro, LEGACY_GYRO, PROS_ERR_, |state| {
+        *state = AdiPortState::default();
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn ext_adi_led_init(smart_port: u8, adi_port: u8) -> i32 {
+    with_adi_port(smart_port, adi_port, PROS_ERR_, |state| {
+        *state = AdiPortState { config: DIGITAL_OUT, ..AdiPortState::default() };
+        merge_ports(smart_port, port_index(adi_port).unwrap_or_default())
+    })
+}
+
+pub unsafe fn ext_adi_led_set(led: i32, buffer: *mut u32, buffer_length: u32) -> i32 {
+    if buffer.is_null() {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+
+    let pixels = core::slice::from_raw_parts(buffer, buffer_length as usize);
+    adi_handle(led, DIGITAL_OUT, PROS_ERR_, |state| {
+        state.pixels = pixels.to_vec();
+        PROS_SUCCESS
+    })
+}
@@ -0,0 +1,110 @@
+//! Simulated controller functions of `pros/misc.h`
+
+use core::ffi::CStr;
+use alloc::string::ToString;
+use crate::{controller::{LCD_COLUMNS, LCD_LINES, LCD_UPDATE_INTERVAL}, error::PROSErr, sim::controller::ControllerDevice};
+use super::{fail, PROS_ERR_, PROS_SUCCESS};
+
+/// Runs a closure on a controller, failing with `EINVAL` if the controller id is invalid
+fn controller<R>(id: u32, sentinel: R, f: impl FnOnce(&mut ControllerDevice) -> R) -> R {
+    if id > 1 {
+        return fail(PROSErr::Invalid, sentinel);
+    }
+
+    crate::sim::with(|brain| f(&mut brain.controllers[id as usize]))
+}
+
+/// Updates a controller's screen, silently dropping the update (like a real controller) if it comes too soon after the last one
+fn update_screen(id: u32, f: impl FnOnce(&mut [[char; LCD_COLUMNS as usize]; LCD_LINES as usize])) -> i32 {
+    let micros = crate::sim::with(|brain| brain.micros);
+
+    controller(id, PROS_ERR_, |controller| {
+        let dropped = controller.last_screen_update
+            .is_some_and(|last| micros - last < LCD_UPDATE_INTERVAL as u64 * 1000);
+        if controller.connected && !dropped {
+            f(&mut controller.screen);
+            controller.last_screen_update = Some(micros);
+        }
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn controller_is_connected(id: u32) -> i32 {
+    controller(id, PROS_ERR_, |controller| controller.connected as i32)
+}
+
+pub unsafe fn controller_get_battery_level(id: u32) -> i32 {
+    controller(id, PROS_ERR_, |controller| if controller.connected { controller.battery_level } else { 0 })
+}
+
+pub unsafe fn controller_get_battery_capacity(id: u32) -> i32 {
+    controller(id, PROS_ERR_, |controller| if controller.connected { controller.battery_capacity } else { 0 })
+}
+
+pub unsafe fn controller_get_analog(id: u32, channel: u32) -> i32 {
+    if channel > 3 {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+
+    controller(id, PROS_ERR_, |controller| {
+        if controller.connected { controller.analog[channel as usize] } else { 0 }
+    })
+}
+
+pub unsafe fn controller_get_digital(id: u32, button: u32) -> i32 {
+    if !(6..=17).contains(&button) {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+
+    controller(id, PROS_ERR_, |controller| {
+        (controller.connected && controller.digital[button as usize - 6]) as i32
+    })
+}
+
+pub unsafe fn controller_get_digital_new_press(id: u32, button: u32) -> i32 {
+    if !(6..=17).contains(&button) {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+
+    controller(id, PROS_ERR_, |controller| {
+        let index = button as usize - 6;
+        let pressed = controller.connected && controller.digital[index];
+        let new_press = pressed && !controller.last_press[index];
+        controller.last_press[index] = pressed;
+        new_press as i32
+    })
+}
+
+pub unsafe fn controller_rumble(id: u32, rumble_pattern: *const u8) -> i32 {
+    let rumble_pattern = CStr::from_ptr(rumble_pattern as *const core::ffi::c_char).to_string_lossy().to_string();
+
+    controller(id, PROS_ERR_, |controller| {
+        controller.rumble = Some(rumble_pattern);
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn controller_set_text(id: u32, line: u8, col: u8, str_: *const u8) -> i32 {
+    if line >= LCD_LINES || col >= LCD_COLUMNS {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+    let text = CStr::from_ptr(str_ as *const core::ffi::c_char).to_string_lossy().to_string();
+
+    update_screen(id, |screen| {
+        for (cell, c) in screen[line as usize][col as usize..].iter_mut().zip(text.chars()) {
+            *cell = c;
+        }
+    })
+}
+
+pub unsafe fn controller_clear_line(id: u32, line: u8) -> i32 {
+    if line >= LCD_LINES {
+        return fail(PROSErr::Invalid, PROS_ERR_);
+    }
+
+    update_screen(id, |screen| screen[line as usize] = [' '; LCD_COLUMNS as usize])
+}
+
+pub unsafe fn controller_clear(id: u32) -> i32 {
+    update_screen(id, |screen| *screen = [[' '; LCD_COLUMNS as usize]; LCD_LINES as usize])
+}
@@ -0,0 +1,138 @@
+//! Simulated SD card functions of `pros/misc.h` and the C standard library
+
+use core::{ffi::{c_char, c_void, CStr}, ptr};
+use alloc::string::{String, ToString};
+use crate::{error::PROSErr, sim::fs::OpenFile};
+use super::{fail, PROS_ERR_, PROS_SUCCESS};
+
+/// The `EOF` value returned by the C standard library on errors
+const EOF: i32 = -1;
+
+/// An opaque C filestream
+#[repr(C)]
+pub struct FILE {
+    _private: [u8; 0],
+}
+
+/// Reads a C string into an owned string
+unsafe fn read_cstr(string: *const u8) -> String {
+    CStr::from_ptr(string as *const c_char).to_string_lossy().to_string()
+}
+
+pub unsafe fn usd_is_installed() -> i32 {
+    crate::sim::with(|brain| brain.usd.is_some() as i32)
+}
+
+pub unsafe fn usd_list_files(path: *const u8, buffer: *mut u8, len: i32) -> i32 {
+    // directories are given relative to the root of the SD card
+    let dir = read_cstr(path).replace('\\', "/");
+    let dir = dir.trim_matches('/');
+
+    let listing = crate::sim::with(|brain| {
+        let files = brain.usd.as_ref()?;
+        let names = files.keys()
+            .filter_map(|path| path.strip_prefix("/usd/"))
+            .filter_map(|path| match path.rsplit_once('/') {
+                Some((parent, name)) if parent == dir => Some(name),
+                None if dir.is_empty() => Some(path),
+                _ => None,
+            })
+            .collect::<alloc::vec::Vec<_>>();
+        Some(names.join("\n"))
+    });
+
+    let Some(listing) = listing else {
+        return fail(PROSErr::IO, PROS_ERR_);
+    };
+
+    // the listing must fit in the buffer along with it's nul terminator
+    if listing.len() >= len.max(0) as usize {
+        return fail(PROSErr::NoBuffSpace, PROS_ERR_);
+    }
+    ptr::copy_nonoverlapping(listing.as_ptr(), buffer, listing.len());
+    *buffer.add(listing.len()) = 0;
+
+    PROS_SUCCESS
+}
+
+pub unsafe fn fopen(filename: *const u8, mode: *const u8) -> *mut FILE {
+    let path = read_cstr(filename);
+    let mode = read_cstr(mode);
+
+    if !path.starts_with("/usd/") {
+        return fail(PROSErr::NoEntry, ptr::null_mut());
+    }
+
+    crate::sim::with(|brain| {
+        let Some(files) = brain.usd.as_mut() else {
+            return fail(PROSErr::IO, ptr::null_mut());
+        };
+
+        let writable = match mode.trim_end_matches('b') {
+            "w" => {
+                files.insert(path.clone(), String::new());
+                true
+            },
+            "a" => {
+                files.entry(path.clone()).or_default();
+                true
+            },
+            "r" if files.contains_key(&path) => false,
+            "r" => return fail(PROSErr::NoEntry, ptr::null_mut()),
+            _ => return fail(PROSErr::Invalid, ptr::null_mut()),
+        };
+
+        // files are handed out as non-null handles that are never dereferenced
+        let handle = brain.next_file;
+        brain.next_file += 1;
+        brain.files.insert(handle, OpenFile { path, writable, position: 0, error: false });
+        ptr::without_provenance_mut(handle)
+    })
+}
+
+pub unsafe fn fputs(s: *const u8, stream: *mut FILE) -> i32 {
+    let string = read_cstr(s);
+
+    crate::sim::with(|brain| {
+        let Some(file) = brain.files.get_mut(&stream.addr()).filter(|file| file.writable) else {
+            return fail(PROSErr::Invalid, EOF);
+        };
+        let Some(contents) = brain.usd.as_mut().and_then(|files| files.get_mut(&file.path)) else {
+            file.error = true;
+            return fail(PROSErr::IO, EOF);
+        };
+
+        contents.push_str(&string);
+        0
+    })
+}
+
+pub unsafe fn fread(ptr: *mut c_void, size: usize, nobj: usize, stream: *mut FILE) -> usize {
+    crate::sim::with(|brain| {
+        let Some(file) = brain.files.get_mut(&stream.addr()).filter(|file| !file.writable) else {
+            return fail(PROSErr::Invalid, 0);
+        };
+        let Some(contents) = brain.usd.as_ref().and_then(|files| files.get(&file.path)) else {
+            file.error = true;
+            return fail(PROSErr::IO, 0);
+        };
+
+        // only whole objects are read
+        let remaining = &contents.as_bytes()[file.position.min(contents.len())..];
+        let read = remaining.len().checked_div(size).unwrap_or(0).min(nobj);
+        ptr::copy_nonoverlapping(remaining.as_ptr(), ptr as *mut u8, read * size);
+        file.position += read * size;
+        read
+    })
+}
+
+pub unsafe fn ferror(stream: *mut FILE) -> i32 {
+    crate::sim::with(|brain| brain.files.get(&stream.addr()).is_some_and(|file| file.error) as i32)
+}
+
+pub unsafe fn fclose(stream: *mut FILE) -> i32 {
+    crate::sim::with(|brain| match brain.files.remove(&stream.addr()) {
+        Some(_) => 0,
+        None => fail(PROSErr::Invalid, EOF),
+    })
+}
@@ -0,0 +1,263 @@
+//! Simulated `pros/imu.h`
+
+use crate::{error::PROSErr, sim::imu::{Imu, Offsets, CALIBRATION_TIME}};
+use super::{claim, fail, PROS_ERR_, PROS_ERR_F_, PROS_SUCCESS};
+
+pub type imu_status_e = u32;
+pub type imu_status_e_t = imu_status_e;
+
+pub const imu_status_e_E_IMU_STATUS_READY: imu_status_e = 0;
+pub const imu_status_e_E_IMU_STATUS_CALIBRATING: imu_status_e = 1;
+pub const imu_status_e_E_IMU_STATUS_ERROR: imu_status_e = 0xFF;
+
+#[repr(C)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct imu_raw_s {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+pub type imu_accel_s_t = imu_raw_s;
+pub type imu_gyro_s_t = imu_raw_s;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct quaternion_s {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+pub type quaternion_s_t = quaternion_s;
+
+#[repr(C, packed)]
+#[derive(Debug, Default, Copy, Clone)]
+pub struct euler_s {
+    pub pitch: f64,
+    pub roll: f64,
+    pub yaw: f64,
+}
+
+pub type euler_s_t = euler_s;
+
+/// Claims a calibrated inertial sensor, failing with `EAGAIN` while it is still calibrating
+fn claim_imu<R: Copy>(port: u8, sentinel: R, f: impl FnOnce(&mut Imu) -> R) -> R {
+    claim(port, sentinel, |imu: &mut Imu, millis| {
+        if imu.is_calibrating(millis) {
+            fail(PROSErr::Again, sentinel)
+        } else {
+            imu.drift_to(millis);
+            f(imu)
+        }
+    })
+}
+
+/// Bounds an angle in degrees to `-180..180`
+fn bound_180(degrees: f64) -> f64 {
+    (degrees + 180.0).rem_euclid(360.0) - 180.0
+}
+
+pub unsafe fn imu_reset(port: u8) -> i32 {
+    claim(port, PROS_ERR_, |imu: &mut Imu, millis| {
+        if imu.is_calibrating(millis) {
+            return fail(PROSErr::Again, PROS_ERR_);
+        }
+
+        // recalibrating re-zeroes every reading
+        *imu = Imu {
+            calibrating_until: Some(millis.wrapping_add(CALIBRATION_TIME)),
+            drift: imu.drift,
+            drifted_at: millis,
+            data_rate: imu.data_rate,
+            ..Imu::default()
+        };
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_get_status(port: u8) -> imu_status_e_t {
+    claim(port, imu_status_e_E_IMU_STATUS_ERROR, |imu: &mut Imu, millis| {
+        if imu.is_calibrating(millis) {
+            imu_status_e_E_IMU_STATUS_CALIBRATING
+        } else {
+            imu_status_e_E_IMU_STATUS_READY
+        }
+    })
+}
+
+pub unsafe fn imu_set_data_rate(port: u8, rate: u32) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        // rounded down to an increment of 5ms with a minimum of 5ms
+        imu.data_rate = (rate - rate % 5).max(5);
+        PROS_SUCCESS
+    })
+}
+
+/// Gets the tared pitch, roll and yaw of an inertial sensor
+fn euler(imu: &Imu) -> euler_s_t {
+    euler_s {
+        pitch: bound_180(imu.pitch + imu.offsets.pitch),
+        roll: bound_180(imu.roll + imu.offsets.roll),
+        yaw: bound_180(imu.yaw + imu.offsets.yaw),
+    }
+}
+
+pub unsafe fn imu_get_rotation(port: u8) -> f64 {
+    claim_imu(port, PROS_ERR_F_, |imu| imu.rotation + imu.offsets.rotation)
+}
+
+pub unsafe fn imu_get_heading(port: u8) -> f64 {
+    claim_imu(port, PROS_ERR_F_, |imu| (imu.rotation + imu.offsets.heading).rem_euclid(360.0))
+}
+
+pub unsafe fn imu_get_pitch(port: u8) -> f64 {
+    claim_imu(port, PROS_ERR_F_, |imu| euler(imu).pitch)
+}
+
+pub unsafe fn imu_get_roll(port: u8) -> f64 {
+    claim_imu(port, PROS_ERR_F_, |imu| euler(imu).roll)
+}
+
+pub unsafe fn imu_get_yaw(port: u8) -> f64 {
+    claim_imu(port, PROS_ERR_F_, |imu| euler(imu).yaw)
+}
+
+pub unsafe fn imu_get_euler(port: u8) -> euler_s_t {
+    let err = euler_s { pitch: PROS_ERR_F_, roll: PROS_ERR_F_, yaw: PROS_ERR_F_ };
+    claim_imu(port, err, |imu| euler(imu))
+}
+
+pub unsafe fn imu_get_quaternion(port: u8) -> quaternion_s_t {
+    let err = quaternion_s { x: PROS_ERR_F_, y: PROS_ERR_F_, z: PROS_ERR_F_, w: PROS_ERR_F_ };
+    claim_imu(port, err, |imu| {
+        // a z-y-x (yaw, pitch, roll) rotation
+        let euler = euler(imu);
+        let (sr, cr) = (euler.roll.to_radians() / 2.0).sin_cos();
+        let (sp, cp) = (euler.pitch.to_radians() / 2.0).sin_cos();
+        let (sy, cy) = (euler.yaw.to_radians() / 2.0).sin_cos();
+
+        quaternion_s {
+            x: sr * cp * cy - cr * sp * sy,
+            y: cr * sp * cy + sr * cp * sy,
+            z: cr * cp * sy - sr * sp * cy,
+            w: cr * cp * cy + sr * sp * sy,
+        }
+    })
+}
+
+pub unsafe fn imu_get_gyro_rate(port: u8) -> imu_gyro_s_t {
+    let err = imu_raw_s { x: PROS_ERR_F_, y: PROS_ERR_F_, z: PROS_ERR_F_ };
+    claim_imu(port, err, |imu| imu_raw_s {
+        x: imu.gyro_rate[0],
+        y: imu.gyro_rate[1],
+        z: imu.gyro_rate[2],
+    })
+}
+
+pub unsafe fn imu_get_accel(port: u8) -> imu_accel_s_t {
+    let err = imu_raw_s { x: PROS_ERR_F_, y: PROS_ERR_F_, z: PROS_ERR_F_ };
+    claim_imu(port, err, |imu| imu_raw_s {
+        x: imu.accel[0],
+        y: imu.accel[1],
+        z: imu.accel[2],
+    })
+}
+
+pub unsafe fn imu_tare(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets = Offsets {
+            heading: -imu.rotation,
+            rotation: -imu.rotation,
+            pitch: -imu.pitch,
+            roll: -imu.roll,
+            yaw: -imu.yaw,
+        };
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_heading(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.heading = -imu.rotation;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_rotation(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.rotation = -imu.rotation;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_pitch(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.pitch = -imu.pitch;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_roll(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.roll = -imu.roll;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_yaw(port: u8) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.yaw = -imu.yaw;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_tare_euler(port: u8) -> i32 {
+    imu_set_euler(port, euler_s::default())
+}
+
+pub unsafe fn imu_set_heading(port: u8, target: f64) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.heading = target.clamp(0.0, 360.0) - imu.rotation;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_set_rotation(port: u8, target: f64) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.rotation = target - imu.rotation;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_set_pitch(port: u8, target: f64) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.pitch = target.clamp(-180.0, 180.0) - imu.pitch;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_set_roll(port: u8, target: f64) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.roll = target.clamp(-180.0, 180.0) - imu.roll;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_set_yaw(port: u8, target: f64) -> i32 {
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.yaw = target.clamp(-180.0, 180.0) - imu.yaw;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn imu_set_euler(port: u8, target: euler_s_t) -> i32 {
+    let euler_s { pitch, roll, yaw } = target;
+    claim_imu(port, PROS_ERR_, |imu| {
+        imu.offsets.pitch = pitch.clamp(-180.0, 180.0) - imu.pitch;
+        imu.offsets.roll = roll.clamp(-180.0, 180.0) - imu.roll;
+        imu.offsets.yaw = yaw.clamp(-180.0, 180.0) - imu.yaw;
+        PROS_SUCCESS
+    })
+}
@@ -0,0 +1,65 @@
+//! The simulated PROS kernel
+//!
+//! Mirrors the signatures of the raw [`bindings`](crate::bindings) generated from PROS, which re-exports everything in this module when the `sim` feature is enabled
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+#![allow(missing_docs)]
+
+use crate::error::PROSErr;
+use super::{Brain, SmartDevice, SMART_PORTS};
+
+mod adi;
+mod controller;
+mod fs;
+mod imu;
+mod motor;
+mod rotation;
+mod rtos;
+
+pub use adi::*;
+pub use controller::*;
+pub use fs::*;
+pub use imu::*;
+pub use motor::*;
+pub use rotation::*;
+pub use rtos::*;
+
+/// The integer sentinel value returned by PROS on errors
+pub const PROS_ERR_: i32 = i32::MAX;
+/// The floating-point sentinel value returned by PROS on errors
+pub const PROS_ERR_F_: f64 = f64::INFINITY;
+/// The value returned by PROS on success
+pub(crate) const PROS_SUCCESS: i32 = 1;
+/// The SmartPort number that addresses the brain's own ADI ports
+pub const INTERNAL_ADI_PORT: u32 = 22;
+
+/// Gets a mutable pointer to the `errno` value of the current thread
+pub unsafe fn __errno() -> *mut i32 {
+    super::errno_ptr()
+}
+
+/// Sets `errno` and returns the sentinel value provided
+pub(crate) fn fail<R>(err: PROSErr, sentinel: R) -> R {
+    super::set_errno(err);
+    sentinel
+}
+
+/// Claims the device on a SmartPort and runs a closure on it along with the current time in milliseconds
+///
+/// Fails with `ENXIO` if the port is out of range and `ENODEV` if no device of the expected type is plugged in
+pub(crate) fn claim<T: SmartDevice, R>(port: u8, sentinel: R, f: impl FnOnce(&mut T, u32) -> R) -> R {
+    if port == 0 || port as usize > SMART_PORTS {
+        return fail(PROSErr::NXIO, sentinel);
+    }
+
+    super::with(|brain: &mut Brain| {
+        let millis = (brain.micros / 1000) as u32;
+        match brain.ports[port as usize - 1].as_mut().and_then(T::from_device) {
+            Some(device) => Ok(f(device, millis)),
+            None => Err(sentinel),
+        }
+    }).unwrap_or_else(|sentinel| fail(PROSErr::NoDev, sentinel))
+}
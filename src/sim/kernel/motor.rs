@@ -0,0 +1,167 @@
+//! Simulated `pros/motors.h`
+
+use super::{claim, fail, PROS_ERR_, PROS_ERR_F_, PROS_SUCCESS};
+use crate::{error::PROSErr, motor::{BrakeMode, EncoderUnits, Gearset}, sim::motor::{Command, Motor}};
+
+/// Claims the motor on a possibly negative (reversed) port
+fn claim_motor<R>(port: i8, sentinel: R, f: impl FnOnce(&mut Motor, bool) -> R) -> R {
+    claim(port.unsigned_abs(), sentinel, |motor, _| f(motor, port < 0))
+}
+
+/// Negates a value if the motor is reversed
+fn direction<T: core::ops::Neg<Output = T>>(val: T, reversed: bool) -> T {
+    if reversed { -val } else { val }
+}
+
+pub unsafe fn motor_move(port: i8, voltage: i32) -> i32 {
+    // scale `-127..=127` onto `-12000..=12000` the same way PROS does
+    let command = ((voltage + 127) * (12000 - -12000)) / (127 - -127) - 12000;
+    motor_move_voltage(port, command)
+}
+
+pub unsafe fn motor_move_absolute(port: i8, position: f64, velocity: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| {
+        motor.target_position = direction(position, reversed);
+        motor.command = Command::Position { target: motor.target_position, velocity: velocity.abs() };
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_move_relative(port: i8, position: f64, velocity: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| {
+        motor.target_position = motor.readings.position + direction(position, reversed);
+        motor.command = Command::Position { target: motor.target_position, velocity: velocity.abs() };
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_move_velocity(port: i8, velocity: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| {
+        motor.command = Command::Velocity(direction(velocity, reversed));
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_move_voltage(port: i8, voltage: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| {
+        motor.command = Command::Voltage(direction(voltage.clamp(-12000, 12000), reversed));
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_modify_profiled_velocity(port: i8, velocity: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| {
+        // only has an effect while following a profiled movement
+        if let Command::Position { velocity: ref mut profiled, .. } = motor.command {
+            *profiled = velocity.abs();
+        }
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_get_target_position(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, reversed| direction(motor.target_position, reversed))
+}
+
+pub unsafe fn motor_get_target_velocity(port: i8) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| match motor.command {
+        Command::Velocity(velocity) => direction(velocity, reversed),
+        _ => 0,
+    })
+}
+
+pub unsafe fn motor_get_voltage(port: i8) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, reversed| match motor.command {
+        Command::Voltage(voltage) => direction(voltage, reversed),
+        _ => 0,
+    })
+}
+
+pub unsafe fn motor_get_actual_velocity(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, reversed| direction(motor.readings.velocity, reversed))
+}
+
+pub unsafe fn motor_get_current_draw(port: i8) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| motor.readings.current_draw)
+}
+
+pub unsafe fn motor_get_efficiency(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, _| motor.readings.efficiency)
+}
+
+pub unsafe fn motor_get_faults(port: i8) -> u32 {
+    claim_motor(port, PROS_ERR_ as u32, |motor, _| motor.faults.bits())
+}
+
+pub unsafe fn motor_get_position(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, reversed| direction(motor.readings.position, reversed))
+}
+
+pub unsafe fn motor_get_power(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, _| motor.readings.power)
+}
+
+pub unsafe fn motor_get_temperature(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, _| motor.readings.temperature)
+}
+
+pub unsafe fn motor_get_torque(port: i8) -> f64 {
+    claim_motor(port, PROS_ERR_F_, |motor, _| motor.readings.torque)
+}
+
+/// Applies a raw configuration value to a motor, failing with `EINVAL` if it's out of range
+fn configure<T: TryFrom<u32>>(port: i8, raw: u32, set: impl FnOnce(&mut Motor, T)) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| match T::try_from(raw) {
+        Ok(val) => {
+            set(motor, val);
+            PROS_SUCCESS
+        },
+        Err(_) => fail(PROSErr::Invalid, PROS_ERR_),
+    })
+}
+
+pub unsafe fn motor_set_brake_mode(port: i8, mode: u32) -> i32 {
+    configure(port, mode, |motor, mode: BrakeMode| motor.config.brake_mode = mode)
+}
+
+pub unsafe fn motor_set_current_limit(port: i8, limit: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| {
+        motor.config.current_limit = limit;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_set_encoder_units(port: i8, units: u32) -> i32 {
+    configure(port, units, |motor, units: EncoderUnits| motor.config.encoder_units = units)
+}
+
+pub unsafe fn motor_set_gearing(port: i8, gearset: u32) -> i32 {
+    configure(port, gearset, |motor, gearset: Gearset| motor.config.gearset = gearset)
+}
+
+pub unsafe fn motor_set_voltage_limit(port: i8, limit: i32) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| {
+        motor.config.voltage_limit = limit;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn motor_get_brake_mode(port: i8) -> u32 {
+    claim_motor(port, PROS_ERR_ as u32, |motor, _| motor.config.brake_mode as u32)
+}
+
+pub unsafe fn motor_get_current_limit(port: i8) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| motor.config.current_limit)
+}
+
+pub unsafe fn motor_get_encoder_units(port: i8) -> u32 {
+    claim_motor(port, PROS_ERR_ as u32, |motor, _| motor.config.encoder_units as u32)
+}
+
+pub unsafe fn motor_get_gearing(port: i8) -> u32 {
+    claim_motor(port, PROS_ERR_ as u32, |motor, _| motor.config.gearset as u32)
+}
+
+pub unsafe fn motor_get_voltage_limit(port: i8) -> i32 {
+    claim_motor(port, PROS_ERR_, |motor, _| motor.config.voltage_limit)
+}
@@ -0,0 +1,68 @@
+//! Simulated `pros/rotation.h`
+
+use crate::sim::rotation::Rotation;
+use super::{claim, PROS_ERR_, PROS_SUCCESS};
+
+/// Claims the rotation sensor on a SmartPort
+fn claim_rotation<R>(port: u8, sentinel: R, f: impl FnOnce(&mut Rotation) -> R) -> R {
+    claim(port, sentinel, |rotation, _| f(rotation))
+}
+
+pub unsafe fn rotation_reset(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        rotation.position = rotation.angle;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_set_data_rate(port: u8, rate: u32) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        // rounded down to an increment of 5ms with a minimum of 5ms
+        rotation.data_rate = (rate - rate % 5).max(5);
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_set_position(port: u8, position: u32) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        rotation.position = position as i32;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_reset_position(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        rotation.position = 0;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_get_position(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| rotation.position)
+}
+
+pub unsafe fn rotation_get_velocity(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| rotation.velocity)
+}
+
+pub unsafe fn rotation_get_angle(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| rotation.angle)
+}
+
+pub unsafe fn rotation_set_reversed(port: u8, value: bool) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        rotation.reversed = value;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_reverse(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| {
+        rotation.reversed = !rotation.reversed;
+        PROS_SUCCESS
+    })
+}
+
+pub unsafe fn rotation_get_reversed(port: u8) -> i32 {
+    claim_rotation(port, PROS_ERR_, |rotation| rotation.reversed as i32)
+}
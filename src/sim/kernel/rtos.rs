@@ -0,0 +1,23 @@
+//! Simulated timing functions of `pros/rtos.h`
+
+pub unsafe fn millis() -> u32 {
+    crate::sim::with(|brain| (brain.micros / 1000) as u32)
+}
+
+pub unsafe fn micros() -> u64 {
+    crate::sim::with(|brain| brain.micros)
+}
+
+pub unsafe fn task_delay(milliseconds: u32) {
+    crate::sim::advance(milliseconds);
+}
+
+pub unsafe fn task_delay_until(prev_time: *mut u32, delta: u32) {
+    // the millisecond counter wraps around like the real kernel's, so the wake time is compared relative to now
+    let target = (*prev_time).wrapping_add(delta);
+    let remaining = target.wrapping_sub(millis());
+    if (remaining as i32) > 0 {
+        task_delay(remaining);
+    }
+    *prev_time = target;
+}
@@ -0,0 +1,134 @@
+//! # Simulation API
+//!
+//! A host-side simulation of the PROS kernel, enabled with the `sim` feature
+//!
+//! With `sim` enabled, [`bindings`](crate::bindings) no longer links against PROS and instead calls into a pure-rust simulated kernel, so robot code built on `safe-vex` can be compiled and unit-tested on a regular computer. The functions in this module let tests plug in devices, script sensor readings and inspect whatever the robot code commanded.
+//!
+//! Every thread owns its own simulated brain (and `errno`), so tests running in parallel never share devices, files or time. Devices must be plugged in (e.g. with [`motor::plug`]) before they are used, exactly like on a real brain; talking to an empty or mismatched port fails with `PROSErr::NoDev`.
+//!
+//! Simulated time only moves forward through [`advance`] or the [`rtos`](crate::rtos) delay functions, which never actually sleep.
+
+use core::cell::{Cell, RefCell};
+use std::collections::BTreeMap;
+use alloc::string::String;
+use crate::{error::PROSErr, port::SmartPort};
+
+pub mod kernel;
+pub mod adi;
+pub mod controller;
+pub mod fs;
+pub mod imu;
+pub mod motor;
+pub mod rotation;
+
+/// The amount of SmartPorts on the brain
+pub(crate) const SMART_PORTS: usize = 21;
+
+/// A device plugged into a simulated SmartPort
+#[derive(Debug, Clone)]
+pub(crate) enum Device {
+    Motor(motor::Motor),
+    Imu(imu::Imu),
+    Rotation(rotation::Rotation),
+    AdiExpander(Box<adi::Adi>),
+}
+
+/// A device type that can be plugged into a simulated SmartPort
+pub(crate) trait SmartDevice: Sized {
+    /// Gets the device out of a SmartPort if it is of this type
+    fn from_device(device: &mut Device) -> Option<&mut Self>;
+}
+
+/// The complete state of a simulated v5 brain
+#[derive(Debug)]
+pub(crate) struct Brain {
+    /// microseconds since the brain started
+    pub micros: u64,
+    /// the devices plugged into each SmartPort
+    pub ports: [Option<Device>; SMART_PORTS],
+    /// the brain's internal ADI ports
+    pub adi: adi::Adi,
+    /// the master and partner controllers
+    pub controllers: [controller::ControllerDevice; 2],
+    /// the files on the SD card, `None` if no SD card is inserted
+    pub usd: Option<BTreeMap<String, String>>,
+    /// the currently open files, keyed by their handles
+    pub files: BTreeMap<usize, fs::OpenFile>,
+    /// the handle to give the next opened file
+    pub next_file: usize,
+    /// a bit for each bank of ADI ports taken through [`AdiPorts::take`](crate::port::AdiPorts::take), with `0` for the brain's own ports
+    pub adi_banks_taken: u32,
+}
+
+impl Default for Brain {
+    fn default() -> Self {
+        Self {
+            micros: 0,
+            ports: Default::default(),
+            adi: adi::Adi::default(),
+            controllers: [controller::ControllerDevice::connected(), controller::ControllerDevice::default()],
+            usd: None,
+            files: BTreeMap::new(),
+            next_file: 1,
+            adi_banks_taken: 0,
+        }
+    }
+}
+
+std::thread_local! {
+    /// The simulated brain of the current thread
+    static BRAIN: RefCell<Brain> = RefCell::new(Brain::default());
+    /// The `errno` value of the current thread
+    static ERRNO: Cell<i32> = const { Cell::new(0) };
+}
+
+/// Runs a closure with mutable access to the current thread's simulated brain
+pub(crate) fn with<R>(f: impl FnOnce(&mut Brain) -> R) -> R {
+    BRAIN.with(|brain| f(&mut brain.borrow_mut()))
+}
+
+/// Runs a closure on the device plugged into a SmartPort, returning `None` if no device of that type is plugged in
+pub(crate) fn with_device<T: SmartDevice, R>(port: SmartPort, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+    with(|brain| {
+        brain.ports[port as usize - 1]
+            .as_mut()
+            .and_then(T::from_device)
+            .map(f)
+    })
+}
+
+/// Marks a bank of ADI ports as taken on the current thread's simulated brain, returning if it wasn't already
+pub(crate) fn take_adi_bank(index: u32) -> bool {
+    with(|brain| {
+        let bit = 1 << index;
+        let taken = brain.adi_banks_taken & bit != 0;
+        brain.adi_banks_taken |= bit;
+        !taken
+    })
+}
+
+/// Sets the `errno` value of the current thread
+pub(crate) fn set_errno(err: PROSErr) {
+    ERRNO.with(|errno| errno.set(err as i32));
+}
+
+/// Gets a pointer to the `errno` value of the current thread
+pub(crate) fn errno_ptr() -> *mut i32 {
+    ERRNO.with(|errno| errno.as_ptr())
+}
+
+/// Resets the current thread's simulated brain, unplugging every device, removing the SD card, giving back every taken ADI port and rewinding time back to `0`
+pub fn reset() {
+    with(|brain| *brain = Brain::default());
+    set_errno(PROSErr::None);
+}
+
+/// Advances simulated time by a given number of milliseconds
+pub fn advance(milliseconds: u32) {
+    with(|brain| brain.micros += milliseconds as u64 * 1000);
+}
+
+/// Unplugs whatever device is plugged into a SmartPort
+pub fn unplug(port: SmartPort) {
+    with(|brain| brain.ports[port as usize - 1] = None);
+}
@@ -0,0 +1,112 @@
+//! Simulated V5 smart motors
+
+use crate::{motor::{Config, MotorFaults, Telemetry}, port::SmartPort};
+use super::{Device, SmartDevice};
+
+/// The last command sent to a simulated motor
+///
+/// All values are as the motor itself sees them, so commands sent to a reversed motor come out negated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Command {
+    /// Driven at a voltage from `-12000` to `12000`
+    Voltage(i32),
+    /// Held at a velocity in RPM by the motor's onboard PID
+    Velocity(i32),
+    /// Moving to a target position (in encoder units) at a maximum velocity in RPM by the motor's onboard PID
+    Position {
+        /// the absolute target position in encoder units
+        target: f64,
+        /// the maximum velocity of the movement in RPM
+        velocity: i32,
+    },
+}
+
+/// The state of a simulated motor
+#[derive(Debug, Clone)]
+pub(crate) struct Motor {
+    /// the last command sent to the motor
+    pub command: Command,
+    /// the last absolute target position set for the motor in encoder units
+    pub target_position: f64,
+    /// the readings the motor reports, unreversed
+    pub readings: Telemetry,
+    /// the configuration of the motor
+    pub config: Config,
+    /// the faults currently raised by the motor
+    pub faults: MotorFaults,
+}
+
+impl Default for Motor {
+    fn default() -> Self {
+        Self {
+            command: Command::Voltage(0),
+            target_position: 0.0,
+            readings: Telemetry::default(),
+            config: Config::default(),
+            faults: MotorFaults::NONE,
+        }
+    }
+}
+
+impl SmartDevice for Motor {
+    fn from_device(device: &mut Device) -> Option<&mut Self> {
+        match device {
+            Device::Motor(motor) => Some(motor),
+            _ => None,
+        }
+    }
+}
+
+/// Plugs a new motor into a SmartPort, replacing whatever was plugged in before
+pub fn plug(port: SmartPort) {
+    super::with(|brain| brain.ports[port as usize - 1] = Some(Device::Motor(Motor::default())));
+}
+
+/// Gets the last command sent to the motor on a SmartPort
+///
+/// Returns `None` if there is no motor on the port
+pub fn command(port: SmartPort) -> Option<Command> {
+    super::with_device(port, |motor: &mut Motor| motor.command)
+}
+
+/// Gets the voltage last commanded to the motor on a SmartPort from `-12000` to `12000`
+///
+/// The voltage is what the motor itself sees, so commands sent to a reversed motor come out negated. This is `0` while the motor is under velocity or position control
+///
+/// Returns `None` if there is no motor on the port
+pub fn voltage(port: SmartPort) -> Option<i32> {
+    super::with_device(port, |motor: &mut Motor| match motor.command {
+        Command::Voltage(voltage) => voltage,
+        _ => 0,
+    })
+}
+
+/// Sets the position (in encoder units) that the motor on a SmartPort measures
+///
+/// Returns `None` if there is no motor on the port
+pub fn set_position(port: SmartPort, position: f64) -> Option<()> {
+    super::with_device(port, |motor: &mut Motor| motor.readings.position = position)
+}
+
+/// Sets all of the readings that the motor on a SmartPort reports
+///
+/// The readings are what the motor itself sees, so the velocity and position read through a reversed port come out negated
+///
+/// Returns `None` if there is no motor on the port
+pub fn set_telemetry(port: SmartPort, readings: Telemetry) -> Option<()> {
+    super::with_device(port, |motor: &mut Motor| motor.readings = readings)
+}
+
+/// Gets the configuration of the motor on a SmartPort
+///
+/// Returns `None` if there is no motor on the port
+pub fn config(port: SmartPort) -> Option<Config> {
+    super::with_device(port, |motor: &mut Motor| motor.config)
+}
+
+/// Sets the faults currently raised by the motor on a SmartPort
+///
+/// Returns `None` if there is no motor on the port
+pub fn set_faults(port: SmartPort, faults: MotorFaults) -> Option<()> {
+    super::with_device(port, |motor: &mut Motor| motor.faults = faults)
+}
@@ -0,0 +1,72 @@
+//! Simulated V5 rotation sensors
+
+use crate::port::SmartPort;
+use super::{Device, SmartDevice};
+
+/// The state of a simulated rotation sensor
+#[derive(Debug, Clone)]
+pub(crate) struct Rotation {
+    /// the absolute angle of the sensor in centidegrees (`0..36000`)
+    pub angle: i32,
+    /// the cumulative position of the sensor in centidegrees
+    pub position: i32,
+    /// the velocity of the sensor in centidegrees per second
+    pub velocity: i32,
+    /// if the sensor counts clockwise rotations as negative
+    pub reversed: bool,
+    /// the refresh interval in milliseconds
+    pub data_rate: u32,
+}
+
+impl Default for Rotation {
+    fn default() -> Self {
+        Self {
+            angle: 0,
+            position: 0,
+            velocity: 0,
+            reversed: false,
+            data_rate: 10,
+        }
+    }
+}
+
+impl SmartDevice for Rotation {
+    fn from_device(device: &mut Device) -> Option<&mut Self> {
+        match device {
+            Device::Rotation(rotation) => Some(rotation),
+            _ => None,
+        }
+    }
+}
+
+/// Plugs a new rotation sensor into a SmartPort, replacing whatever was plugged in before
+pub fn plug(port: SmartPort) {
+    super::with(|brain| brain.ports[port as usize - 1] = Some(Device::Rotation(Rotation::default())));
+}
+
+/// Physically turns the rotation sensor on a SmartPort clockwise by a number of centidegrees (counterclockwise if negative)
+///
+/// Both the angle and position of the sensor move, in the opposite direction if the sensor is reversed
+///
+/// Returns `None` if there is no rotation sensor on the port
+pub fn turn(port: SmartPort, centidegrees: i32) -> Option<()> {
+    super::with_device(port, |rotation: &mut Rotation| {
+        let delta = if rotation.reversed { -centidegrees } else { centidegrees };
+        rotation.angle = (rotation.angle + delta).rem_euclid(36000);
+        rotation.position += delta;
+    })
+}
+
+/// Sets the velocity (in centidegrees per second) that the rotation sensor on a SmartPort measures
+///
+/// Returns `None` if there is no rotation sensor on the port
+pub fn set_velocity(port: SmartPort, centidegrees_per_sec: i32) -> Option<()> {
+    super::with_device(port, |rotation: &mut Rotation| rotation.velocity = centidegrees_per_sec)
+}
+
+/// Gets the refresh interval (in milliseconds) of the rotation sensor on a SmartPort
+///
+/// Returns `None` if there is no rotation sensor on the port
+pub fn data_rate(port: SmartPort) -> Option<u32> {
+    super::with_device(port, |rotation: &mut Rotation| rotation.data_rate)
+}
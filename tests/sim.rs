@@ -0,0 +1,112 @@
+//! Host-side tests of the wrappers against the simulated PROS kernel
+//!
+//! Run with the `sim` feature on the host's target, see the README's *Testing on your computer* section
+
+#![cfg(feature = "sim")]
+
+use safe_vex::{adi::AdiDigitalOut, bindings, error::{self, PROSErr, PROSResult}, imu, motor, port::{AdiPort, AdiPorts, SmartPort}, rotation, rtos, sim};
+
+#[test]
+fn sentinels_round_trip_errno() {
+    sim::reset();
+
+    // a failing call sets errno for the current thread, which the sentinels pick back up
+    assert_eq!(motor::get_voltage(SmartPort::One, false), Err(PROSErr::NoDev));
+    assert_eq!(error::from_errno(), PROSErr::NoDev);
+    assert_eq!(bindings::PROS_ERR_.check(), Err(PROSErr::NoDev));
+    assert_eq!((bindings::PROS_ERR_ as u32).check(), Err(PROSErr::NoDev));
+    assert_eq!(bindings::PROS_ERR_F_.check(), Err(PROSErr::NoDev));
+
+    // anything other than a sentinel is a success
+    assert_eq!(1i32.check(), Ok(1));
+    assert_eq!(0u32.check(), Ok(0));
+    assert_eq!(1.5f64.check(), Ok(1.5));
+}
+
+#[test]
+fn errno_is_per_thread() {
+    sim::reset();
+    assert_eq!(motor::get_voltage(SmartPort::One, false), Err(PROSErr::NoDev));
+
+    std::thread::spawn(|| assert_eq!(error::from_errno(), PROSErr::None)).join().unwrap();
+    assert_eq!(error::from_errno(), PROSErr::NoDev);
+}
+
+#[test]
+fn empty_ports_are_nodev() {
+    sim::reset();
+
+    assert_eq!(motor::move_voltage(SmartPort::One, false, 12000), Err(PROSErr::NoDev));
+    assert_eq!(imu::get_heading(SmartPort::Two), Err(PROSErr::NoDev));
+    assert_eq!(rotation::get_angle(SmartPort::Three), Err(PROSErr::NoDev));
+    assert_eq!(AdiDigitalOut::new(AdiPorts::take(SmartPort::Four).unwrap().a).unwrap_err().err, PROSErr::NoDev);
+
+    // the wrong kind of device is no better than none at all
+    sim::motor::plug(SmartPort::Five);
+    assert_eq!(imu::get_heading(SmartPort::Five), Err(PROSErr::NoDev));
+
+    // and devices can be unplugged mid-match
+    sim::rotation::plug(SmartPort::Three);
+    assert_eq!(rotation::get_angle(SmartPort::Three), Ok(0));
+    sim::unplug(SmartPort::Three);
+    assert_eq!(rotation::get_angle(SmartPort::Three), Err(PROSErr::NoDev));
+}
+
+#[test]
+fn adi_ports_are_taken_once() {
+    sim::reset();
+    sim::adi::plug_expander(SmartPort::One);
+
+    let ports = AdiPorts::take(SmartPort::One).unwrap();
+    assert!(AdiPorts::take(SmartPort::One).is_none());
+
+    // a handle that fails to be created gives it's port back
+    sim::unplug(SmartPort::One);
+    let err = AdiDigitalOut::new(ports.a).unwrap_err();
+    assert_eq!(err.err, PROSErr::NoDev);
+    sim::adi::plug_expander(SmartPort::One);
+    let out = AdiDigitalOut::new(err.ports).unwrap();
+    assert_eq!(out.into_port().port(), AdiPort::A);
+
+    // and resetting the brain gives every port back
+    sim::reset();
+    assert!(AdiPorts::take(SmartPort::One).is_some());
+}
+
+#[test]
+fn reversed_motors_negate() {
+    sim::reset();
+    sim::motor::plug(SmartPort::One);
+
+    motor::move_voltage(SmartPort::One, true, 6000).unwrap();
+    assert_eq!(sim::motor::voltage(SmartPort::One), Some(-6000));
+    assert_eq!(motor::get_voltage(SmartPort::One, true), Ok(6000));
+    assert_eq!(motor::get_voltage(SmartPort::One, false), Ok(-6000));
+
+    motor::move_i8(SmartPort::One, true, -127).unwrap();
+    assert_eq!(sim::motor::voltage(SmartPort::One), Some(12000));
+
+    sim::motor::set_position(SmartPort::One, 90.0).unwrap();
+    assert_eq!(motor::telemetry(SmartPort::One, true).unwrap().position, -90.0);
+}
+
+#[test]
+fn time_wraps_around() {
+    sim::reset();
+    sim::advance(u32::MAX - 5);
+
+    // waking up past `u32::MAX` wraps around instead of overflowing
+    let mut prev = rtos::millis();
+    rtos::task_delay_until(&mut prev, 10);
+    assert_eq!(prev, 4);
+    assert_eq!(rtos::millis(), 4);
+
+    // and so does calibrating across it
+    sim::advance(u32::MAX - 1004);
+    sim::imu::plug(SmartPort::One);
+    imu::reset(SmartPort::One).unwrap();
+    rtos::task_delay(1999);
+    assert_eq!(imu::get_heading(SmartPort::One), Err(PROSErr::Again));
+    rtos::task_delay(1);
+    assert_eq!(imu::get_heading(SmartPort::One), Ok(0.0));
+}